@@ -0,0 +1,60 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+#[path = "../src/part2/legacy/mod.rs"]
+mod legacy;
+
+use aoc_01::rule::Rule;
+use aoc_01::vocabulary::Vocabulary;
+use aoc_common::input::resolve::Resolver;
+use aoc_common::Input;
+use legacy::get_number;
+
+const ROUNDS: usize = 50;
+
+const PIECES: [&str; 12] = ["one", "twone", "eightwo", "seven", "nine", "3", "7", "x", "q", "abc", "ei", "th"];
+
+// Lines made of digits, words and noise, used when the day's input isn't available.
+fn generated(count: usize) -> Vec<String> {
+    let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+    (0..count)
+        .map(|_| (0..8)
+            .map(|_| {
+                seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                PIECES[(seed >> 33) as usize % PIECES.len()]
+            })
+            .collect())
+        .collect()
+}
+
+fn lines() -> (String, Vec<String>) {
+    match Resolver::from_env(None, env!("CARGO_MANIFEST_DIR")).resolve(1).and_then(Input::read) {
+        Ok(input) => ("the day's input".to_string(), input.lines().map(str::to_string).collect()),
+        Err(_) => ("generated lines".to_string(), generated(1000)),
+    }
+}
+
+fn median<T>(lines: &[String], decode: impl Fn(&str) -> Option<T>) -> Duration {
+    let mut times = (0..ROUNDS)
+        .map(|_| {
+            let start = Instant::now();
+            for line in lines {
+                black_box(decode(black_box(line)));
+            }
+            start.elapsed()
+        })
+        .collect::<Vec<_>>();
+    times.sort();
+    times[ROUNDS / 2]
+}
+
+fn main() {
+    let (source, lines) = lines();
+    let fold = median(&lines, |line| Some(get_number(line)));
+    let rule = Rule::default();
+    let automaton = median(&lines, |line| rule.apply(Vocabulary::english(), line));
+    println!("Decoding {} lines of {}, median of {} rounds", lines.len(), source, ROUNDS);
+    for (name, time) in [("fold", fold), ("automaton", automaton)] {
+        println!("{:<10}  {:>10}  {:>8}/line", name, format!("{:.1?}", time), format!("{:.1?}", time / lines.len().max(1) as u32));
+    }
+    println!("The automaton is {:.1}x faster", fold.as_secs_f64() / automaton.as_secs_f64());
+}
@@ -0,0 +1,253 @@
+use std::collections::VecDeque;
+
+const ROOT: usize = 0;
+const MISSING: u32 = u32::MAX;
+
+// An Aho-Corasick automaton over bytes. The failure links are folded into a full
+// transition table, so each byte costs one lookup, and the bytes that appear in no
+// pattern share a single class to keep that table small.
+#[derive(Clone)]
+struct Automaton<T> {
+    classes: [u16; 256],
+    alphabet: usize,
+    transitions: Vec<u32>,
+    depths: Vec<usize>,
+    outputs: Vec<Option<T>>,
+    // The closest state along the failure links that ends a pattern.
+    dictionary: Vec<Option<usize>>,
+}
+
+impl<T: Copy> Automaton<T> {
+    fn new(patterns: Vec<(Vec<u8>, T)>) -> Self {
+        let mut classes = [0; 256];
+        let mut alphabet = 1;
+        for byte in patterns.iter().flat_map(|(pattern, _)| pattern) {
+            if classes[*byte as usize] == 0 {
+                classes[*byte as usize] = alphabet as u16;
+                alphabet += 1;
+            }
+        }
+        let mut automaton = Self {
+            classes,
+            alphabet,
+            transitions: vec![MISSING; alphabet],
+            depths: vec![0],
+            outputs: vec![None],
+            dictionary: vec![None],
+        };
+        for (pattern, value) in patterns {
+            let mut state = ROOT;
+            for byte in pattern {
+                let slot = automaton.slot(state, byte);
+                if automaton.transitions[slot] == MISSING {
+                    automaton.transitions[slot] = automaton.depths.len() as u32;
+                    automaton.transitions.extend(std::iter::repeat_n(MISSING, alphabet));
+                    automaton.depths.push(automaton.depths[state] + 1);
+                    automaton.outputs.push(None);
+                    automaton.dictionary.push(None);
+                }
+                state = automaton.transitions[slot] as usize;
+            }
+            automaton.outputs[state] = Some(value);
+        }
+
+        // Breadth first, so that a state's failure is complete before the states below it use it.
+        let mut failures = vec![ROOT; automaton.depths.len()];
+        let mut queue = VecDeque::from([ROOT]);
+        while let Some(state) = queue.pop_front() {
+            for class in 0..alphabet {
+                let slot = state * alphabet + class;
+                let fallback = if state == ROOT {
+                    ROOT
+                } else {
+                    automaton.transitions[failures[state] * alphabet + class] as usize
+                };
+                match automaton.transitions[slot] {
+                    MISSING => automaton.transitions[slot] = fallback as u32,
+                    child => {
+                        let child = child as usize;
+                        failures[child] = fallback;
+                        automaton.dictionary[child] = if automaton.outputs[fallback].is_some() {
+                            Some(fallback)
+                        } else {
+                            automaton.dictionary[fallback]
+                        };
+                        queue.push_back(child);
+                    }
+                }
+            }
+        }
+        automaton
+    }
+
+    fn slot(&self, state: usize, byte: u8) -> usize {
+        state * self.alphabet + self.classes[byte as usize] as usize
+    }
+
+    fn next(&self, state: usize, byte: u8) -> usize {
+        self.transitions[self.slot(state, byte)] as usize
+    }
+
+    // The patterns ending at a state, longest first, with their lengths.
+    fn outputs(&self, state: usize) -> impl Iterator<Item = (usize, T)> + '_ {
+        let first = match self.outputs[state] {
+            Some(_) => Some(state),
+            None => self.dictionary[state],
+        };
+        std::iter::successors(first, |state| self.dictionary[*state])
+            .filter_map(|state| self.outputs[state].map(|value| (self.depths[state], value)))
+    }
+
+    // The match that starts first, and of those the longest, as its start, length and
+    // value. A pattern inside a longer one ends first, so the scan only stops once no
+    // match in progress can start any earlier or grow any longer.
+    fn earliest(&self, bytes: impl Iterator<Item = u8>) -> Option<(usize, usize, T)> {
+        let mut state = ROOT;
+        let mut earliest = None::<(usize, usize, T)>;
+        for (index, byte) in bytes.enumerate() {
+            state = self.next(state, byte);
+            for (len, value) in self.outputs(state) {
+                let start = index + 1 - len;
+                if earliest.is_none_or(|(first, first_len, _)| start < first || (start == first && len > first_len)) {
+                    earliest = Some((start, len, value));
+                }
+            }
+            if earliest.is_some_and(|(start, _, _)| start + self.depths[state] < index + 1) {
+                break;
+            }
+        }
+        earliest
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Match<T> {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    pub(crate) value: T,
+}
+
+// Finds the first and the last of a set of possibly overlapping patterns in one pass
+// each: forwards for the first, and backwards, over the reversed patterns, for the last.
+#[derive(Clone)]
+pub(crate) struct Matcher<T> {
+    forward: Automaton<T>,
+    backward: Automaton<T>,
+}
+
+impl<T: Copy> Matcher<T> {
+    pub(crate) fn new<'a>(patterns: impl IntoIterator<Item = (&'a str, T)>) -> Self {
+        let patterns = patterns.into_iter()
+            .filter(|(pattern, _)| !pattern.is_empty())
+            .map(|(pattern, value)| (pattern.as_bytes().to_vec(), value))
+            .collect::<Vec<_>>();
+        let reversed = patterns.iter()
+            .map(|(pattern, value)| (pattern.iter().rev().copied().collect(), *value))
+            .collect();
+        Self {
+            forward: Automaton::new(patterns),
+            backward: Automaton::new(reversed),
+        }
+    }
+
+    // The match that starts first, the longest one if several start there.
+    pub(crate) fn first(&self, line: &str) -> Option<Match<T>> {
+        self.first_from(line, 0)
+    }
+
+    // The same, among the matches that start at byte `from` or after it.
+    pub(crate) fn first_from(&self, line: &str, from: usize) -> Option<Match<T>> {
+        self.forward.earliest(line.bytes().skip(from))
+            .map(|(start, len, value)| Match { start: from + start, end: from + start + len, value })
+    }
+
+    // Every match, overlapping ones included, by start then end.
+    pub(crate) fn all(&self, line: &str) -> Vec<Match<T>> {
+        let mut state = ROOT;
+        let mut matches = Vec::new();
+        for (index, byte) in line.bytes().enumerate() {
+            state = self.forward.next(state, byte);
+            matches.extend(self.forward.outputs(state)
+                .map(|(len, value)| Match { start: index + 1 - len, end: index + 1, value }));
+        }
+        matches.sort_by_key(|found| (found.start, found.end));
+        matches
+    }
+
+    // The match that ends last, the longest one if several end there, which is the
+    // first one of the reversed line.
+    pub(crate) fn last(&self, line: &str) -> Option<Match<T>> {
+        self.last_before(line, line.len())
+    }
+
+    // The same, among the matches that end at byte `to` or before it.
+    pub(crate) fn last_before(&self, line: &str, to: usize) -> Option<Match<T>> {
+        self.backward.earliest(line.as_bytes()[..to].iter().rev().copied())
+            .map(|(start, len, value)| Match { start: to - start - len, end: to - start, value })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn matcher() -> Matcher<u32> {
+        Matcher::new([("one", 1), ("two", 2), ("eight", 8), ("abcd", 4), ("bc", 5), ("c", 6)])
+    }
+
+    #[test]
+    fn test_first() {
+        let matcher = matcher();
+        assert_eq!(Some(Match { start: 1, end: 6, value: 8 }), matcher.first("xeightwo"));
+        assert_eq!(Some(Match { start: 0, end: 3, value: 2 }), matcher.first("twone"));
+        assert_eq!(Some(Match { start: 1, end: 5, value: 4 }), matcher.first("xabcd"));
+        assert_eq!(Some(Match { start: 1, end: 3, value: 5 }), matcher.first("xbcd"));
+        assert_eq!(None, matcher.first("tw on"));
+        assert_eq!(None, matcher.first(""));
+    }
+
+    #[test]
+    fn test_last() {
+        let matcher = matcher();
+        assert_eq!(Some(Match { start: 5, end: 8, value: 2 }), matcher.last("xeightwo"));
+        assert_eq!(Some(Match { start: 2, end: 5, value: 1 }), matcher.last("twone"));
+        assert_eq!(Some(Match { start: 0, end: 4, value: 4 }), matcher.last("abcd"));
+        assert_eq!(Some(Match { start: 1, end: 3, value: 5 }), matcher.last("abcx"));
+        assert_eq!(None, matcher.last("ne"));
+    }
+
+    #[test]
+    fn test_bounded() {
+        let matcher = matcher();
+        assert_eq!(Some(Match { start: 5, end: 8, value: 2 }), matcher.first_from("xeightwo", 2));
+        assert_eq!(None, matcher.first_from("xeightwo", 6));
+        assert_eq!(Some(Match { start: 1, end: 6, value: 8 }), matcher.last_before("xeightwo", 7));
+        assert_eq!(None, matcher.last_before("xeightwo", 5));
+    }
+
+    #[test]
+    fn test_all() {
+        let found = matcher().all("xeightwobcd")
+            .into_iter()
+            .map(|found| (found.start, found.end, found.value))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![(1, 6, 8), (5, 8, 2), (8, 10, 5), (9, 10, 6)], found);
+        assert!(matcher().all("").is_empty());
+    }
+
+    #[test]
+    fn test_nested() {
+        let matcher = Matcher::new([("I", 1), ("II", 2), ("III", 3), ("V", 5), ("VIII", 8)]);
+        assert_eq!(Some(Match { start: 1, end: 5, value: 8 }), matcher.first("xVIIIx"));
+        assert_eq!(Some(Match { start: 1, end: 5, value: 8 }), matcher.last("xVIIIx"));
+        assert_eq!(Some(Match { start: 1, end: 4, value: 3 }), matcher.first("xIIIV"));
+        assert_eq!(Some(Match { start: 4, end: 5, value: 5 }), matcher.last("xIIIV"));
+    }
+
+    #[test]
+    fn test_non_ascii() {
+        let matcher = Matcher::new([("un", 1), ("été", 2)]);
+        assert_eq!(Some(Match { start: 1, end: 6, value: 2 }), matcher.first("xétéun"));
+        assert_eq!(Some(Match { start: 6, end: 8, value: 1 }), matcher.last("xétéun"));
+    }
+}
@@ -0,0 +1,157 @@
+use std::fmt;
+use aoc_common::Input;
+use crate::automaton::Match;
+use crate::missing::Missing;
+use crate::rule::{Number, Rule};
+use crate::vocabulary::Vocabulary;
+
+// The tokens found in a line, the digits the rule builds part 2's value from, and the
+// values the line gets in each part with the rule and the missing digits policy.
+struct Trace<'a> {
+    number: usize,
+    missing: Missing,
+    line: &'a str,
+    matches: Vec<Match<u32>>,
+    chosen: Vec<(Match<u32>, &'static str)>,
+    part1: Option<Number>,
+    part2: Option<Number>,
+}
+
+impl<'a> Trace<'a> {
+    fn new(number: usize, line: &'a str, vocabulary: &Vocabulary, rule: &Rule, missing: &Missing) -> Self {
+        let digits = vocabulary.digit_matches(line);
+        let chosen = digits.iter()
+            .enumerate()
+            .filter_map(|(index, found)| Some((*found, rule.role(index, digits.len())?)))
+            .collect();
+        Self {
+            number,
+            missing: *missing,
+            line,
+            matches: vocabulary.all(line),
+            chosen,
+            part1: rule.apply(Vocabulary::digits(), line),
+            part2: rule.apply(vocabulary, line),
+        }
+    }
+
+    fn differs(&self) -> bool {
+        self.part1 != self.part2
+    }
+}
+
+impl Trace<'_> {
+    fn value(&self, value: &Option<Number>) -> String {
+        match (value, self.missing) {
+            (Some(value), _) => value.to_string(),
+            (None, Missing::Error) => "no value, an error".to_string(),
+            (None, Missing::Skip) => "no value, skipped".to_string(),
+            (None, Missing::Default(value)) => format!("no value, counted as {}", value),
+        }
+    }
+}
+
+impl fmt::Display for Trace<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let marker = if self.differs() { '*' } else { ' ' };
+        writeln!(f, "{} Line {}: {}", marker, self.number, self.line)?;
+        let spans = self.matches.iter()
+            .map(|found| format!("{}..{}", found.start, found.end))
+            .collect::<Vec<_>>();
+        let span_width = spans.iter().map(String::len).max().unwrap_or(0);
+        let token_width = self.matches.iter()
+            .map(|found| self.line[found.start..found.end].chars().count())
+            .max()
+            .unwrap_or(0);
+        for (found, span) in self.matches.iter().zip(&spans) {
+            let role = self.chosen.iter()
+                .find(|(digit, _)| digit == found)
+                .map_or_else(String::new, |(_, role)| format!("  {}", role));
+            let token = &self.line[found.start..found.end];
+            writeln!(f, "      {:<span_width$}  {:<token_width$}  {}{}", span, token, found.value, role)?;
+        }
+        if self.matches.is_empty() {
+            writeln!(f, "      no tokens")?;
+        }
+        write!(f, "    part 1: {}, part 2: {}", self.value(&self.part1), self.value(&self.part2))
+    }
+}
+
+// Every line's trace, with the lines whose parts disagree marked, such as "eightwo",
+// where part 1 sees no digit at all.
+pub fn explain(input: &Input, vocabulary: &Vocabulary, rule: &Rule, missing: &Missing) -> String {
+    let traces = input.lines()
+        .enumerate()
+        .map(|(index, line)| Trace::new(index + 1, line, vocabulary, rule, missing))
+        .collect::<Vec<_>>();
+    let differing = traces.iter().filter(|trace| trace.differs()).count();
+    let mut text = traces.iter()
+        .map(|trace| format!("{}\n", trace))
+        .collect::<String>();
+    text.push_str(&format!(
+        "{} line(s), {} where part 1 and part 2 differ, marked with '*'",
+        traces.len(),
+        differing
+    ));
+    text
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_explain() {
+        let text = explain(&Input::from("xtwone3four\n1abc2\nzero\n"), Vocabulary::english(), &Rule::default(), &Missing::Skip);
+        let expected = "\
+* Line 1: xtwone3four
+      1..4   two   2  first
+      3..6   one   1
+      6..7   3     3
+      7..11  four  4  last
+    part 1: 33, part 2: 24
+  Line 2: 1abc2
+      0..1  1  1  first
+      4..5  2  2  last
+    part 1: 12, part 2: 12
+  Line 3: zero
+      no tokens
+    part 1: no value, skipped, part 2: no value, skipped
+3 line(s), 1 where part 1 and part 2 differ, marked with '*'";
+        assert_eq!(expected, text);
+    }
+
+    #[test]
+    fn test_single_token() {
+        let trace = Trace::new(1, "eightwo", &Vocabulary::from_spec("digits+english").unwrap(), &Rule::default(), &Missing::Error);
+        assert!(trace.differs());
+        assert_eq!("* Line 1: eightwo\n      0..5  eight  8  first\n      4..7  two    2  last\n    part 1: no value, an error, part 2: 82", trace.to_string());
+
+        let trace = Trace::new(2, "xsevenx", Vocabulary::english(), &Rule::default(), &Missing::Error);
+        assert!(trace.to_string().contains("1..6  seven  7  first, last"));
+
+        let trace = Trace::new(3, "two1nine", Vocabulary::english(), &Rule::All, &Missing::Default(4));
+        assert!(trace.to_string().ends_with("part 1: 1, part 2: 219"));
+
+        let trace = Trace::new(4, "abc", Vocabulary::english(), &Rule::default(), &Missing::Default(4));
+        assert!(trace.to_string().ends_with("part 1: no value, counted as 4, part 2: no value, counted as 4"));
+    }
+
+    #[test]
+    fn test_rule_markers() {
+        let trace = Trace::new(1, "xtwone3four", Vocabulary::english(), &Rule::Nth(2), &Missing::Error);
+        assert_eq!(
+            "* Line 1: xtwone3four\n      1..4   two   2\n      3..6   one   1  chosen\n      6..7   3     3\n      7..11  four  4\n    part 1: no value, an error, part 2: 1",
+            trace.to_string()
+        );
+
+        let trace = Trace::new(2, "a1b2c3", Vocabulary::english(), &Rule::FirstLast(2), &Missing::Error);
+        assert!(trace.to_string().contains("1..2  1  1  first\n      3..4  2  2  first, last\n      5..6  3  3  last"));
+
+        let roman = Vocabulary::from_spec("roman").unwrap();
+        let trace = Trace::new(3, "xVIIIxIV", &roman, &Rule::All, &Missing::Error);
+        let text = trace.to_string();
+        assert!(text.contains("1..5  VIII  8  chosen") && text.contains("6..8  IV    4  chosen"), "{}", text);
+        assert!(!text.contains("I     1  chosen"), "{}", text);
+    }
+}
@@ -0,0 +1,202 @@
+mod automaton;
+pub mod explain;
+pub mod missing;
+pub mod part1;
+pub mod part2;
+pub mod rule;
+pub mod vocabulary;
+
+use std::borrow::Cow;
+use std::io::BufRead;
+use aoc_common::{stream_lines, Answer, Error, Input, Options, Solution};
+use missing::Missing;
+use rule::{Number, Rule};
+use vocabulary::Vocabulary;
+
+pub struct Day01;
+
+// The calibration document, the vocabulary its part 2 digits are written with, the
+// rule each line's value is built with, and what lines without digits are worth,
+// along with each line's value in both parts, None where the rule finds no number.
+pub struct Document {
+    input: Input,
+    vocabulary: Cow<'static, Vocabulary>,
+    rule: Rule,
+    missing: Missing,
+    values: [Vec<Option<Number>>; 2],
+}
+
+fn settings(options: &Options) -> aoc_common::Result<(Cow<'static, Vocabulary>, Rule, Missing)> {
+    options.expect_only(&[vocabulary::OPTION, rule::OPTION, missing::OPTION])?;
+    let vocabulary = match options.get(vocabulary::OPTION) {
+        Some(spec) => Cow::Owned(Vocabulary::from_spec(spec)?),
+        None => Cow::Borrowed(Vocabulary::english()),
+    };
+    let rule = options.get(rule::OPTION)
+        .map(str::parse)
+        .transpose()?
+        .unwrap_or_default();
+    let missing = options.get(missing::OPTION)
+        .map(str::parse)
+        .transpose()?
+        .unwrap_or_default();
+    Ok((vocabulary, rule, missing))
+}
+
+impl Document {
+    pub(crate) fn new(input: &Input, vocabulary: Cow<'static, Vocabulary>, rule: Rule, missing: Missing) -> Self {
+        let decode = |vocabulary: &Vocabulary| input.lines()
+            .map(|line| rule.apply(vocabulary, line))
+            .collect::<Vec<_>>();
+        let values = [decode(Vocabulary::digits()), decode(&vocabulary)];
+        Document { input: input.clone(), vocabulary, rule, missing, values }
+    }
+
+    pub(crate) fn total(&self, part: u8) -> aoc_common::Result<Number> {
+        self.missing.total(&self.input, &self.values[part as usize - 1], &self.rule)
+    }
+}
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+
+    // Each line is decoded once for both parts, straight from the input's buffer.
+    type Input = Document;
+
+    fn parse(input: &Input) -> aoc_common::Result<Self::Input> {
+        Self::parse_with(input, &Options::default())
+    }
+
+    fn parse_with(input: &Input, options: &Options) -> aoc_common::Result<Self::Input> {
+        let (vocabulary, rule, missing) = settings(options)?;
+        Ok(Document::new(input, vocabulary, rule, missing))
+    }
+
+    fn part1(input: &Self::Input) -> aoc_common::Result<Answer> {
+        Ok(part1::solve(input)?.into())
+    }
+
+    fn part2(input: &Self::Input) -> aoc_common::Result<Answer> {
+        Ok(part2::solve(input)?.into())
+    }
+
+    fn warnings(input: &Self::Input, part: u8) -> Vec<String> {
+        input.missing.report(&input.values[part as usize - 1], &input.rule)
+            .into_iter()
+            .collect()
+    }
+
+    fn explain(input: &Self::Input) -> aoc_common::Result<String> {
+        Ok(explain::explain(&input.input, &input.vocabulary, &input.rule, &input.missing))
+    }
+
+    fn stream(reader: &mut dyn BufRead, parts: &[u8]) -> aoc_common::Result<Vec<Answer>> {
+        Self::stream_with(reader, parts, &Options::default())
+    }
+
+    fn stream_with(reader: &mut dyn BufRead, parts: &[u8], options: &Options) -> aoc_common::Result<Vec<Answer>> {
+        let (vocabulary, rule, missing) = settings(options)?;
+        if let Some(part) = parts.iter().find(|part| !(1..=2).contains(*part)) {
+            return Err(Error::Unsolved { day: Self::DAY, part: *part });
+        }
+        let mut sums = vec![Number::default(); parts.len()];
+        for (index, line) in stream_lines(reader).enumerate() {
+            let line = line?;
+            for (sum, part) in sums.iter_mut().zip(parts) {
+                let number = if *part == 1 {
+                    part1::get_number(&line, &rule, &missing)
+                } else {
+                    missing.value(&vocabulary, &rule, &line)
+                };
+                if let Some(number) = number.map_err(|error| error.at_line(index + 1))? {
+                    *sum = sum.add(&number);
+                }
+            }
+        }
+        Ok(sums.into_iter().map(Answer::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::BufReader;
+    use aoc_common::{get_input_lines, Runner};
+    use super::*;
+
+    #[test]
+    fn test_get_input_lines() {
+        const LINES: [&str; 4] = [
+            "1abc2",
+            "pqr3stu8vwx",
+            "a1b2c3d4e5f",
+            "treb7uchet",
+        ];
+
+        assert_eq!(LINES, get_input_lines("./src/input/examples/example1.txt").unwrap().as_slice());
+    }
+
+    #[test]
+    fn test_stream() {
+        let file = std::fs::File::open("./src/input/examples/example2.txt").unwrap();
+        let input = Input::read("./src/input/examples/example2.txt").unwrap();
+
+        assert_eq!(Day01.run(&input, &[2]).unwrap(), Day01.stream(&mut BufReader::new(file), &[2]).unwrap());
+        assert_eq!(vec![Answer::Unsigned(142)], Day01.stream(&mut "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n\n".as_bytes(), &[1]).unwrap());
+    }
+
+    #[test]
+    fn test_vocabulary_option() {
+        let input = Input::from("un2trois\nquatre5six\n");
+        let options = "vocabulary=digits+french".parse::<Options>().unwrap();
+        let parsed = Day01.parse_with(&input, &options).unwrap();
+        assert_eq!(Answer::Unsigned(22 + 55), Day01.solve(&parsed, 1).unwrap());
+        assert_eq!(Answer::Unsigned(13 + 46), Day01.solve(&parsed, 2).unwrap());
+        assert_eq!(vec![Answer::Unsigned(13 + 46)], Day01.stream_with(&mut input.as_bytes(), &[2], &options).unwrap());
+        assert_eq!(Answer::Unsigned(22 + 56), Day01.solve(&Day01.parse(&input).unwrap(), 2).unwrap());
+
+        let unknown = "rules=sum".parse::<Options>().unwrap();
+        assert!(matches!(Day01.parse_with(&input, &unknown), Err(Error::Option { name, .. }) if name == "rules"));
+    }
+
+    #[test]
+    fn test_rule_option() {
+        let input = Input::from("un2trois\nquatre5six\n");
+        let options = "vocabulary=digits+french,rule=all".parse::<Options>().unwrap();
+        let parsed = Day01.parse_with(&input, &options).unwrap();
+        assert_eq!(Answer::Unsigned(2 + 5), Day01.solve(&parsed, 1).unwrap());
+        assert_eq!(Answer::Unsigned(123 + 456), Day01.solve(&parsed, 2).unwrap());
+        assert_eq!(vec![Answer::Unsigned(123 + 456)], Day01.stream_with(&mut input.as_bytes(), &[2], &options).unwrap());
+
+        let bad = "rule=middle".parse::<Options>().unwrap();
+        assert!(matches!(Day01.parse_with(&input, &bad), Err(Error::Option { name, .. }) if name == "rule"));
+    }
+
+    #[test]
+    fn test_missing_option() {
+        let input = Input::from("1abc2\nnada\ntwo\n");
+        let parsed = Day01.parse(&input).unwrap();
+        assert!(matches!(Day01.solve(&parsed, 1), Err(Error::Parse(error)) if error.line == 2));
+        assert!(matches!(Day01.solve(&parsed, 2), Err(Error::Parse(error)) if error.line == 2));
+        assert!(Day01.warnings(&parsed, 2).is_empty());
+
+        let options = "missing=default:5".parse::<Options>().unwrap();
+        let parsed = Day01.parse_with(&input, &options).unwrap();
+        assert_eq!(Answer::Unsigned(12 + 5 + 5), Day01.solve(&parsed, 1).unwrap());
+        assert_eq!(Answer::Unsigned(12 + 5 + 22), Day01.solve(&parsed, 2).unwrap());
+        assert_eq!(vec!["2 line(s) without at least one digit counted as 5: 2, 3"], Day01.warnings(&parsed, 1));
+        assert_eq!(vec!["1 line(s) without at least one digit counted as 5: 2"], Day01.warnings(&parsed, 2));
+
+        let options = "missing=skip".parse::<Options>().unwrap();
+        assert_eq!(
+            vec![Answer::Unsigned(12), Answer::Unsigned(12 + 22)],
+            Day01.stream_with(&mut input.as_bytes(), &[1, 2], &options).unwrap()
+        );
+        assert!(matches!(Day01.stream(&mut input.as_bytes(), &[2]), Err(Error::Parse(error)) if error.line == 2));
+    }
+}
+
+aoc_common::example_tests!(Day01);
@@ -0,0 +1,176 @@
+use std::fmt;
+use std::str::FromStr;
+use aoc_common::{Error, Input};
+use crate::rule::{Number, Rule};
+use crate::vocabulary::Vocabulary;
+
+pub(crate) const OPTION: &str = "missing";
+
+// The most affected lines a warning lists.
+const LISTED: usize = 10;
+
+// What a line without the digits the rule needs is worth, the same in both parts:
+// an error, nothing, or a default value.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Missing {
+    #[default]
+    Error,
+    Skip,
+    Default(u64),
+}
+
+impl Missing {
+    // The line's value, or None if it's skipped.
+    pub fn value(&self, vocabulary: &Vocabulary, rule: &Rule, line: &str) -> aoc_common::Result<Option<Number>> {
+        self.resolve(rule, line, rule.apply(vocabulary, line))
+    }
+
+    // The same, for a line the rule already found `number` in.
+    pub fn resolve(&self, rule: &Rule, line: &str, number: Option<Number>) -> aoc_common::Result<Option<Number>> {
+        match (number, self) {
+            (Some(number), _) => Ok(Some(number)),
+            (None, Missing::Error) => Err(Error::parse(line, line, &rule.expected())),
+            (None, Missing::Skip) => Ok(None),
+            (None, Missing::Default(value)) => Ok(Some(Number::Small(*value))),
+        }
+    }
+
+    // Adds up the input's lines, given the number the rule found in each of them.
+    pub fn total(&self, input: &Input, values: &[Option<Number>], rule: &Rule) -> aoc_common::Result<Number> {
+        let mut total = Number::default();
+        for (index, (line, value)) in input.lines().zip(values).enumerate() {
+            let resolved = self.resolve(rule, line, value.clone()).map_err(|error| error.at_line(index + 1))?;
+            if let Some(number) = resolved {
+                total = total.add(&number);
+            }
+        }
+        Ok(total)
+    }
+
+    // Names the lines the policy was applied to, which an error already does.
+    pub fn report(&self, values: &[Option<Number>], rule: &Rule) -> Option<String> {
+        let action = match self {
+            Missing::Error => return None,
+            Missing::Skip => "skipped".to_string(),
+            Missing::Default(value) => format!("counted as {}", value),
+        };
+        let affected = values.iter()
+            .enumerate()
+            .filter(|(_, value)| value.is_none())
+            .map(|(index, _)| index + 1)
+            .collect::<Vec<_>>();
+        if affected.is_empty() {
+            return None;
+        }
+        let mut lines = affected.iter()
+            .take(LISTED)
+            .map(usize::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        if affected.len() > LISTED {
+            lines.push_str(&format!(" and {} more", affected.len() - LISTED));
+        }
+        Some(format!(
+            "{} line(s) without {} {}: {}",
+            affected.len(),
+            rule.expected().trim_start_matches("a line with "),
+            action,
+            lines
+        ))
+    }
+}
+
+impl FromStr for Missing {
+    type Err = Error;
+
+    fn from_str(string: &str) -> aoc_common::Result<Self> {
+        match string.split_once(':') {
+            None if string == "error" => Ok(Missing::Error),
+            None if string == "skip" => Ok(Missing::Skip),
+            None if string == "default" => Ok(Missing::Default(0)),
+            Some(("default", value)) => value.parse()
+                .map(Missing::Default)
+                .map_err(|_| Error::Option {
+                    name: OPTION.to_string(),
+                    reason: format!("'{}' isn't a number", value),
+                }),
+            _ => Err(Error::Option {
+                name: OPTION.to_string(),
+                reason: format!("'{}' isn't one of: error, skip, default[:N]", string),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Missing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Missing::Error => write!(f, "error"),
+            Missing::Skip => write!(f, "skip"),
+            Missing::Default(value) => write!(f, "default:{}", value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "two1nine\nabc\neightwothree\n7\n";
+
+    fn values(input: &Input, vocabulary: &Vocabulary, rule: &Rule) -> Vec<Option<Number>> {
+        input.lines()
+            .map(|line| rule.apply(vocabulary, line))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        for missing in ["error", "skip", "default:5"] {
+            assert_eq!(missing, missing.parse::<Missing>().unwrap().to_string());
+        }
+        assert_eq!(Missing::Default(0), "default".parse().unwrap());
+        assert!(matches!("default:x".parse::<Missing>(), Err(Error::Option { name, .. }) if name == OPTION));
+        assert!(matches!("zero".parse::<Missing>(), Err(Error::Option { .. })));
+    }
+
+    #[test]
+    fn test_total() {
+        let input = Input::from(INPUT);
+        let rule = Rule::default();
+        let digits = values(&input, Vocabulary::digits(), &rule);
+
+        let error = Missing::Error.total(&input, &digits, &rule).unwrap_err();
+        assert!(matches!(error, Error::Parse(error) if error.line == 2 && error.text == "abc"));
+        assert_eq!(Number::Small(11 + 77), Missing::Skip.total(&input, &digits, &rule).unwrap());
+        assert_eq!(Number::Small(11 + 5 + 5 + 77), Missing::Default(5).total(&input, &digits, &rule).unwrap());
+
+        // Both parts follow the policy, part 2 only misses the line with no token at all.
+        let english = values(&input, Vocabulary::english(), &rule);
+        let error = Missing::Error.total(&input, &english, &rule).unwrap_err();
+        assert!(matches!(error, Error::Parse(error) if error.line == 2));
+        assert_eq!(Number::Small(29 + 83 + 77), Missing::Skip.total(&input, &english, &rule).unwrap());
+    }
+
+    #[test]
+    fn test_report() {
+        let input = Input::from(INPUT);
+        let (digits, rule) = (Vocabulary::digits(), Rule::default());
+        assert_eq!(None, Missing::Error.report(&values(&input, digits, &rule), &rule));
+        assert_eq!(
+            Some("2 line(s) without at least one digit skipped: 2, 3".to_string()),
+            Missing::Skip.report(&values(&input, digits, &rule), &rule)
+        );
+        assert_eq!(
+            Some("4 line(s) without at least 2 digits counted as 0: 1, 2, 3, 4".to_string()),
+            Missing::Default(0).report(&values(&input, digits, &Rule::Nth(2)), &Rule::Nth(2))
+        );
+        assert_eq!(None, Missing::Skip.report(&values(&Input::from("1\n"), digits, &rule), &rule));
+
+        let many = Input::from("x\n".repeat(12));
+        assert_eq!(
+            Some("12 line(s) without at least one digit skipped: 1, 2, 3, 4, 5, 6, 7, 8, 9, 10 and 2 more".to_string()),
+            Missing::Skip.report(&values(&many, digits, &rule), &rule)
+        );
+    }
+}
@@ -1,45 +1,59 @@
-fn get_number(line: &str) -> u32 {
-    let digits = line.chars()
-        .filter_map(|c| c.to_digit(10))
-        .collect::<Vec<u32>>();
-    digits[0] * 10 + digits[digits.len() - 1]
+use crate::missing::Missing;
+use crate::rule::{Number, Rule};
+use crate::vocabulary::Vocabulary;
+use crate::Document;
+
+pub(crate) fn get_number(line: &str, rule: &Rule, missing: &Missing) -> aoc_common::Result<Option<Number>> {
+    missing.value(Vocabulary::digits(), rule, line)
 }
 
-pub(crate) fn solve(lines: &[String]) -> u32 {
-    lines.iter()
-        .map(|line| get_number(line))
-        .sum()
+pub fn solve(input: &Document) -> aoc_common::Result<Number> {
+    input.total(1)
 }
 
 #[cfg(test)]
 mod test {
+    use std::borrow::Cow;
+    use aoc_common::{Answer, Error, Input, Solution};
+    use crate::Day01;
     use super::*;
 
     #[test]
     fn test_get_number() {
-        const LINES: [&str; 4] = [
+        const LINES: [&str; 5] = [
             "1abc2",
             "pqr3stu8vwx",
             "a1b2c3d4e5f",
             "treb7uchet",
+            "two1nine",
         ];
 
-        const NUMS: [u32; 4] = [12, 38, 15, 77];
+        const NUMS: [u64; 5] = [12, 38, 15, 77, 11];
 
         for (line, num) in LINES.iter().zip(NUMS) {
-            assert_eq!(num, get_number(line));
+            assert_eq!(Some(Number::Small(num)), get_number(line, &Rule::default(), &Missing::Error).unwrap());
         }
     }
 
+    #[test]
+    fn test_get_number_without_digit() {
+        let document = |input: &str, rule| Document::new(&Input::from(input), Cow::Borrowed(Vocabulary::english()), rule, Missing::Error);
+        let error = solve(&document("1abc2\ntreb7uchet\neightwothree", Rule::default())).unwrap_err();
+        assert!(matches!(error, Error::Parse(error) if (error.line, error.column) == (3, 1) && error.text == "eightwothree"));
+
+        let error = solve(&document("1abc2\ntreb7uchet", Rule::Nth(2))).unwrap_err();
+        assert!(matches!(error, Error::Parse(error) if error.line == 2 && error.expected == "a line with at least 2 digits"));
+        assert_eq!(None, get_number("eightwothree", &Rule::default(), &Missing::Skip).unwrap());
+    }
+
     #[test]
     fn test_solve() {
-        let lines = [
-            "1abc2".to_string(),
-            "pqr3stu8vwx".to_string(),
-            "a1b2c3d4e5f".to_string(),
-            "treb7uchet".to_string(),
-        ];
+        let input = Day01::parse(&Input::from("\
+1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet")).unwrap();
 
-        assert_eq!(142, solve(lines.as_ref()));
+        assert_eq!(Answer::Unsigned(142), Day01::part1(&input).unwrap());
     }
 }
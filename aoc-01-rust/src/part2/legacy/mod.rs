@@ -0,0 +1,55 @@
+pub(crate) const VALID_DIGITS: [&str; 18] = [
+    "1", "2", "3", "4", "5", "6", "7", "8" ,"9",
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+pub(crate) fn index_to_digit(index: usize) -> u32 {
+    (index % 9 + 1) as u32
+}
+
+// The original decoder, which scans the line once per entry of VALID_DIGITS. It's kept
+// as the reference the vocabulary's automaton is checked and benchmarked against, by
+// the tests and the get_number bench, which both include this file.
+#[derive(Debug, PartialEq)]
+pub(crate) struct DigitMatches {
+    pub(crate) digit: u32,
+    pub(crate) first_index: usize,
+    pub(crate) last_index: usize,
+}
+
+pub(crate) fn get_digit_matches(line: &str) -> Vec<DigitMatches> {
+    VALID_DIGITS.iter().enumerate()
+        .filter_map(|(index, digit)| {
+            let matches = line.match_indices(digit).collect::<Vec<_>>();
+            if matches.is_empty() {
+                return None;
+            }
+            Some(DigitMatches {
+                digit: index_to_digit(index),
+                first_index: matches[0].0,
+                last_index: matches[matches.len() - 1].0,
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn get_number(line: &str) -> u32 {
+    let matches = get_digit_matches(line);
+    let (first_digit, _, last_digit, _) = matches.iter()
+        .fold((0, usize::MAX, 0, usize::MIN), |(first_digit, first_index, last_digit, last_index), matches| {
+            let mut new_first_digit = first_digit;
+            let mut new_first_index = first_index;
+            let mut new_last_digit = last_digit;
+            let mut new_last_index = last_index;
+            if matches.first_index < first_index {
+                new_first_digit = matches.digit;
+                new_first_index = matches.first_index;
+            }
+            if matches.last_index > last_index {
+                new_last_digit = matches.digit;
+                new_last_index = matches.last_index;
+            }
+            (new_first_digit, new_first_index, new_last_digit, new_last_index)
+        });
+    first_digit * 10 + last_digit
+}
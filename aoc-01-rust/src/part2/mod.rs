@@ -1,68 +1,27 @@
-const VALID_DIGITS: [&str; 18] = [
-    "1", "2", "3", "4", "5", "6", "7", "8" ,"9",
-    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
-];
+use crate::rule::Number;
+use crate::Document;
 
-fn index_to_digit(index: usize) -> u32 {
-    (index % 9 + 1) as u32
+pub fn solve(input: &Document) -> aoc_common::Result<Number> {
+    input.total(2)
 }
 
-#[derive(Debug, PartialEq)]
-struct DigitMatches {
-    digit: u32,
-    first_index: usize,
-    last_index: usize,
-}
-
-fn get_digit_matches(line: &str) -> Vec<DigitMatches> {
-    VALID_DIGITS.iter().enumerate()
-        .filter_map(|(index, digit)| {
-            let matches = line.match_indices(digit).collect::<Vec<_>>();
-            if matches.is_empty() {
-                return None;
-            }
-            Some(DigitMatches {
-                digit: index_to_digit(index),
-                first_index: matches[0].0,
-                last_index: matches[matches.len() - 1].0,
-            })
-        })
-        .collect()
-}
-
-fn get_number(line: &str) -> u32 {
-    let matches = get_digit_matches(line);
-    let (first_digit, _, last_digit, _) = matches.iter()
-        .fold((0, usize::MAX, 0, usize::MIN), |(first_digit, first_index, last_digit, last_index), matches| {
-            let mut new_first_digit = first_digit;
-            let mut new_first_index = first_index;
-            let mut new_last_digit = last_digit;
-            let mut new_last_index = last_index;
-            if matches.first_index < first_index {
-                new_first_digit = matches.digit;
-                new_first_index = matches.first_index;
-            }
-            if matches.last_index > last_index {
-                new_last_digit = matches.digit;
-                new_last_index = matches.last_index;
-            }
-            (new_first_digit, new_first_index, new_last_digit, new_last_index)
-        });
-    first_digit * 10 + last_digit
-}
-
-pub(crate) fn solve(lines: &[String]) -> u32 {
-    lines.iter()
-        .map(|line| get_number(line))
-        .sum()
-}
+#[cfg(test)]
+mod legacy;
 
 #[cfg(test)]
 mod test {
+    use std::borrow::Cow;
     use std::cmp::Ordering;
+    use aoc_common::{Answer, Input, Solution};
+    use crate::missing::Missing;
+    use crate::rule::Rule;
+    use crate::vocabulary::Vocabulary;
+    use crate::Day01;
+    use super::legacy::*;
     use super::*;
 
     #[test]
+    #[allow(clippy::needless_range_loop)]
     fn test_index_to_digit() {
         const DIGITS: [u32; VALID_DIGITS.len()] = [
             1, 2, 3, 4, 5, 6, 7, 8, 9,
@@ -127,17 +86,62 @@ mod test {
     }
 
     #[test]
-    fn test_solve() {
-        let lines = [
-            "two1nine".to_string(),
-            "eightwothree".to_string(),
-            "abcone2threexyz".to_string(),
-            "xtwone3four".to_string(),
-            "4nineeightseven2".to_string(),
-            "zoneight234".to_string(),
-            "7pqrstsixteen".to_string(),
+    fn test_number_like_get_number() {
+        const LINES: [&str; 6] = [
+            "twone",
+            "oneight",
+            "sevenine",
+            "eighthree",
+            "nineeight9eightwo",
+            "oonetwothreee",
         ];
 
-        assert_eq!(281, solve(lines.as_ref()));
+        for line in LINES {
+            assert_eq!(Some(get_number(line)), Vocabulary::english().number(line), "{}", line);
+        }
+
+        // The fold counts a line without any digit as 0, where the automaton finds no number.
+        for line in ["abc", ""] {
+            assert_eq!(0, get_number(line));
+            assert_eq!(None, Vocabulary::english().number(line));
+        }
+    }
+
+    #[test]
+    fn test_only_token_at_line_start() {
+        // The fold's last index starts at 0, so it misses a last digit at the start of the
+        // line. That's how the day's input once gave 53136 for part 2 instead of 53268.
+        for (line, fold, number) in [("6lxpdpdnr", 60, 66), ("one", 10, 11), ("eightxyz", 80, 88)] {
+            assert_eq!(fold, get_number(line), "{}", line);
+            assert_eq!(Some(number), Vocabulary::english().number(line), "{}", line);
+            assert_eq!(Answer::Unsigned(number as u64), Day01::part2(&Day01::parse(&Input::from(line)).unwrap()).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_solve_with_vocabulary() {
+        let input = Input::from("deux1neuf\ntwo1nine\nzero\n");
+        let english = || Cow::Borrowed(Vocabulary::english());
+        let spec = |spec| Cow::Owned(Vocabulary::from_spec(spec).unwrap());
+        let solved = |vocabulary, rule, missing| solve(&Document::new(&input, vocabulary, rule, missing));
+        assert_eq!(Number::Small(11 + 29), solved(english(), Rule::default(), Missing::Skip).unwrap());
+        assert_eq!(Number::Small(29 + 11), solved(spec("digits+french"), Rule::default(), Missing::Skip).unwrap());
+        assert_eq!(Number::Small(11 + 11), solved(spec("zero+digits"), Rule::default(), Missing::Skip).unwrap());
+        assert_eq!(Number::Small(1 + 219), solved(english(), Rule::All, Missing::Skip).unwrap());
+        assert!(solved(english(), Rule::default(), Missing::Error).is_err());
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = Day01::parse(&Input::from("\
+two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen")).unwrap();
+
+        assert_eq!(Answer::Unsigned(281), Day01::part2(&input).unwrap());
     }
 }
@@ -0,0 +1,262 @@
+use std::fmt;
+use std::str::FromStr;
+use aoc_common::{Answer, BigUint, Error};
+use crate::vocabulary::Vocabulary;
+
+pub(crate) const OPTION: &str = "rule";
+
+// How a line's value is built from its digits: the first and last K digits, all
+// of them, the Nth one (from 1), or their sum. The puzzle's own is 'first-last',
+// the first digit then the last one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Rule {
+    FirstLast(usize),
+    All,
+    Nth(usize),
+    Sum,
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Rule::FirstLast(1)
+    }
+}
+
+impl Rule {
+    pub(crate) fn expected(&self) -> String {
+        match self {
+            Rule::Nth(position) if *position > 1 => format!("a line with at least {} digits", position),
+            _ => "a line with at least one digit".to_string(),
+        }
+    }
+
+    // The line's value, or None when it doesn't have the digits the rule needs.
+    pub fn apply(&self, vocabulary: &Vocabulary, line: &str) -> Option<Number> {
+        let chosen = match *self {
+            // Only the digits taken are looked for, from either end of the line, so the
+            // puzzle's own rule stops at the first and the last one.
+            Rule::FirstLast(count) => {
+                let first = vocabulary.first_digits(line, count);
+                let last = if first.len() < count {
+                    first.clone()
+                } else {
+                    vocabulary.last_digits(line, count)
+                };
+                first.into_iter().chain(last).collect()
+            }
+            _ => {
+                let digits = vocabulary.digits_in(line);
+                self.positions(digits.len())
+                    .into_iter()
+                    .map(|index| digits[index])
+                    .collect::<Vec<_>>()
+            }
+        };
+        if chosen.is_empty() {
+            return None;
+        }
+        match self {
+            Rule::Sum => Some(Number::Small(chosen.iter().map(|digit| *digit as u64).sum())),
+            _ => Some(Number::concat(&chosen)),
+        }
+    }
+
+    // The indices of the digits the value is built from, in order, out of `count`. A line
+    // with fewer than K digits uses them all on both sides of 'first-last:K', as a single
+    // digit is both the first and the last one.
+    fn positions(&self, count: usize) -> Vec<usize> {
+        match *self {
+            Rule::FirstLast(first_last) => {
+                let first_last = first_last.min(count);
+                (0..first_last).chain(count - first_last..count).collect()
+            }
+            Rule::All | Rule::Sum => (0..count).collect(),
+            Rule::Nth(position) => position.checked_sub(1)
+                .filter(|index| *index < count)
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    // What the digit at `index`, out of `count`, is used as, for explain to mark it.
+    pub(crate) fn role(&self, index: usize, count: usize) -> Option<&'static str> {
+        match *self {
+            Rule::FirstLast(first_last) => {
+                let first_last = first_last.min(count);
+                match (index < first_last, index + first_last >= count) {
+                    (true, true) => Some("first, last"),
+                    (true, false) => Some("first"),
+                    (false, true) => Some("last"),
+                    (false, false) => None,
+                }
+            }
+            _ => self.positions(count).contains(&index).then_some("chosen"),
+        }
+    }
+}
+
+fn count(name: &str, value: Option<&str>) -> aoc_common::Result<usize> {
+    value.unwrap_or("1")
+        .parse::<usize>()
+        .ok()
+        .filter(|count| *count > 0)
+        .ok_or_else(|| Error::Option {
+            name: OPTION.to_string(),
+            reason: format!("'{}' takes a number from 1, as in '{}:2'", name, name),
+        })
+}
+
+impl FromStr for Rule {
+    type Err = Error;
+
+    fn from_str(string: &str) -> aoc_common::Result<Self> {
+        let (name, value) = match string.split_once(':') {
+            Some((name, value)) => (name, Some(value)),
+            None => (string, None),
+        };
+        match (name, value) {
+            ("first-last", _) => Ok(Rule::FirstLast(count(name, value)?)),
+            ("nth", Some(_)) => Ok(Rule::Nth(count(name, value)?)),
+            ("all", None) => Ok(Rule::All),
+            ("sum", None) => Ok(Rule::Sum),
+            _ => Err(Error::Option {
+                name: OPTION.to_string(),
+                reason: format!("'{}' isn't one of: first-last[:K], all, nth:N, sum", string),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rule::FirstLast(1) => write!(f, "first-last"),
+            Rule::FirstLast(count) => write!(f, "first-last:{}", count),
+            Rule::All => write!(f, "all"),
+            Rule::Nth(position) => write!(f, "nth:{}", position),
+            Rule::Sum => write!(f, "sum"),
+        }
+    }
+}
+
+// A value that stays a u64 until it no longer fits, since concatenated digits
+// quickly outgrow it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Number {
+    Small(u64),
+    Big(BigUint),
+}
+
+impl Default for Number {
+    fn default() -> Self {
+        Number::Small(0)
+    }
+}
+
+impl Number {
+    fn concat<'a>(digits: impl IntoIterator<Item = &'a u32>) -> Self {
+        digits.into_iter().fold(Number::default(), |number, digit| match number {
+            Number::Small(value) => match value.checked_mul(10).and_then(|value| value.checked_add(*digit as u64)) {
+                Some(value) => Number::Small(value),
+                None => Number::Big(BigUint::from(value).mul_small(10).add(&BigUint::from(*digit as u64))),
+            },
+            Number::Big(value) => Number::Big(value.mul_small(10).add(&BigUint::from(*digit as u64))),
+        })
+    }
+
+    fn to_big(&self) -> BigUint {
+        match self {
+            Number::Small(value) => BigUint::from(*value),
+            Number::Big(value) => value.clone(),
+        }
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        match (self, other) {
+            (Number::Small(a), Number::Small(b)) => match a.checked_add(*b) {
+                Some(sum) => Number::Small(sum),
+                None => Number::Big(self.to_big().add(&other.to_big())),
+            },
+            _ => Number::Big(self.to_big().add(&other.to_big())),
+        }
+    }
+}
+
+impl std::iter::Sum for Number {
+    fn sum<I: Iterator<Item = Self>>(numbers: I) -> Self {
+        numbers.fold(Number::default(), |sum, number| sum.add(&number))
+    }
+}
+
+impl From<Number> for Answer {
+    fn from(number: Number) -> Self {
+        match number {
+            Number::Small(value) => Answer::Unsigned(value),
+            Number::Big(value) => Answer::Big(value),
+        }
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Number::Small(value) => write!(f, "{}", value),
+            Number::Big(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn apply(rule: &str, line: &str) -> Option<String> {
+        rule.parse::<Rule>().unwrap()
+            .apply(Vocabulary::english(), line)
+            .map(|number| number.to_string())
+    }
+
+    #[test]
+    fn test_parse() {
+        for rule in ["first-last", "first-last:3", "all", "nth:2", "sum"] {
+            assert_eq!(rule, rule.parse::<Rule>().unwrap().to_string());
+        }
+        assert_eq!(Rule::default(), "first-last:1".parse().unwrap());
+        for rule in ["first-last:0", "nth", "nth:x", "all:2", "last"] {
+            assert!(matches!(rule.parse::<Rule>(), Err(Error::Option { name, .. }) if name == OPTION), "{}", rule);
+        }
+    }
+
+    #[test]
+    fn test_apply() {
+        assert_eq!(Some("83".to_string()), apply("first-last", "eightwothree"));
+        assert_eq!(Some("8223".to_string()), apply("first-last:2", "eightwothree"));
+        assert_eq!(Some("77".to_string()), apply("first-last:2", "treb7uchet"));
+        assert_eq!(Some("823".to_string()), apply("all", "eightwothree"));
+        assert_eq!(Some("2".to_string()), apply("nth:2", "eightwothree"));
+        assert_eq!(None, apply("nth:4", "eightwothree"));
+        assert_eq!(None, Rule::Nth(0).apply(Vocabulary::english(), "eightwothree"));
+        assert_eq!(Some("13".to_string()), apply("sum", "eightwothree"));
+        assert_eq!(None, apply("sum", "abc"));
+        assert_eq!(
+            Some("123456789123456789123".to_string()),
+            apply("all", "one2three4five6seven8nine123456789one2three")
+        );
+    }
+
+    #[test]
+    fn test_nested_tokens() {
+        let roman = Vocabulary::from_spec("roman").unwrap();
+        assert_eq!(Some(Number::Small(84)), Rule::All.apply(&roman, "xVIIIxIV"));
+        assert_eq!(Some(Number::Small(84)), Rule::default().apply(&roman, "xVIIIxIV"));
+    }
+
+    #[test]
+    fn test_sum() {
+        let numbers = [Number::Small(u64::MAX), Number::Small(2), Number::Small(3)];
+        let sum = numbers.into_iter().sum::<Number>();
+        assert_eq!("18446744073709551620", sum.to_string());
+        assert!(matches!(Answer::from(sum), Answer::Big(_)));
+        assert_eq!(Answer::Unsigned(5), Answer::from([Number::Small(2), Number::Small(3)].into_iter().sum::<Number>()));
+    }
+}
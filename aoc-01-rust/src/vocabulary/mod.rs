@@ -0,0 +1,304 @@
+use std::fmt;
+use std::path::Path;
+use std::sync::OnceLock;
+use aoc_common::{read_input, Error, Result};
+use crate::automaton::{Match, Matcher};
+
+pub(crate) const OPTION: &str = "vocabulary";
+
+const DIGIT: &str = "a digit from 0 to 9";
+
+const DIGITS: [&str; 9] = ["1", "2", "3", "4", "5", "6", "7", "8", "9"];
+const ENGLISH: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+const FRENCH: [&str; 9] = ["un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf"];
+const ROMAN: [&str; 9] = ["I", "II", "III", "IV", "V", "VI", "VII", "VIII", "IX"];
+
+const BUILTINS: [&str; 5] = ["digits", "zero", "english", "french", "roman"];
+
+// The sets other than zero list the tokens for 1 to 9.
+fn builtin(name: &str) -> Option<Vec<(&'static str, u32)>> {
+    let words = match name {
+        "digits" => DIGITS,
+        "zero" => return Some(vec![("0", 0), ("zero", 0)]),
+        "english" => ENGLISH,
+        "french" => FRENCH,
+        "roman" => ROMAN,
+        _ => return None,
+    };
+    Some(words.into_iter().zip(1..).collect())
+}
+
+// The tokens a calibration line's digits are written with, and their values. When
+// tokens overlap, the first digit is the token that starts first and the last digit
+// the one that ends last, the longest of them on a tie, so "VIII" is 8 either way.
+#[derive(Clone)]
+pub struct Vocabulary {
+    tokens: Vec<(String, u32)>,
+    matcher: Matcher<u32>,
+}
+
+impl Vocabulary {
+    pub fn new<'a>(tokens: impl IntoIterator<Item = (&'a str, u32)>) -> Result<Self> {
+        let mut vocabulary = Vec::new();
+        for (token, value) in tokens {
+            // A value isn't read from any text here, so there's no line to point at.
+            if value > 9 {
+                return Err(Error::Option {
+                    name: OPTION.to_string(),
+                    reason: format!("'{}' is given {}, not {}", token, value, DIGIT),
+                });
+            }
+            add(&mut vocabulary, token, value).map_err(|expected| Error::parse(token, token, &expected))?;
+        }
+        Ok(Self::build(vocabulary))
+    }
+
+    // A token file has a 'token = value' line per token, where values are single digits.
+    // Blank lines and lines starting with '#' are skipped.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut vocabulary = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+            let Some((token, value)) = line.split_once('=') else {
+                return Err(Error::parse(line, line, "'token = value'").at_line(index + 1));
+            };
+            let (token, value) = (token.trim(), value.trim());
+            let digit = value.parse::<u32>()
+                .ok()
+                .filter(|digit| *digit <= 9)
+                .ok_or_else(|| Error::parse(line, value, DIGIT).at_line(index + 1))?;
+            add(&mut vocabulary, token, digit)
+                .map_err(|expected| Error::parse(line, token, &expected).at_line(index + 1))?;
+        }
+        Ok(Self::build(vocabulary))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Self::parse(&read_input(path)?)
+    }
+
+    // Built-in sets and token files joined with '+', such as 'digits+french' or
+    // 'digits+zero+tokens.txt'.
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let mut tokens = Vec::new();
+        for name in spec.split('+').map(str::trim) {
+            let vocabulary = match builtin(name) {
+                Some(tokens) => Self::build(tokens.into_iter().map(|(token, value)| (token.to_string(), value)).collect()),
+                None if Path::new(name).is_file() => Self::load(name)?,
+                None => return Err(Error::Option {
+                    name: OPTION.to_string(),
+                    reason: format!(
+                        "'{}' is neither a token file nor one of: {}",
+                        name,
+                        BUILTINS.join(", ")
+                    ),
+                }),
+            };
+            tokens.extend(vocabulary.tokens);
+        }
+        Self::new(tokens.iter().map(|(token, value)| (token.as_str(), *value)))
+    }
+
+    fn build(tokens: Vec<(String, u32)>) -> Self {
+        let matcher = Matcher::new(tokens.iter().map(|(token, value)| (token.as_str(), *value)));
+        Self { tokens, matcher }
+    }
+
+    // Part 1's digits.
+    pub fn digits() -> &'static Self {
+        static DIGITS_ONLY: OnceLock<Vocabulary> = OnceLock::new();
+        DIGITS_ONLY.get_or_init(|| Self::from_spec("digits").expect("The built-in sets should be valid"))
+    }
+
+    // Part 2's digits and English words, the puzzle's own.
+    pub fn english() -> &'static Self {
+        static ENGLISH_WORDS: OnceLock<Vocabulary> = OnceLock::new();
+        ENGLISH_WORDS.get_or_init(|| Self::from_spec("digits+english").expect("The built-in sets should be valid"))
+    }
+
+    pub fn tokens(&self) -> &[(String, u32)] {
+        &self.tokens
+    }
+
+    pub(crate) fn first(&self, line: &str) -> Option<Match<u32>> {
+        self.matcher.first(line)
+    }
+
+    pub(crate) fn last(&self, line: &str) -> Option<Match<u32>> {
+        self.matcher.last(line)
+    }
+
+    pub(crate) fn all(&self, line: &str) -> Vec<Match<u32>> {
+        self.matcher.all(line)
+    }
+
+    // The line's digits in order, overlapping tokens included, but not those inside a
+    // longer token, so "eightwo" has 8 and 2 while "VIII" only has 8.
+    pub fn digits_in(&self, line: &str) -> Vec<u32> {
+        self.digit_matches(line)
+            .into_iter()
+            .map(|found| found.value)
+            .collect()
+    }
+
+    // The first `count` digits of `digits_in`, without scanning the line past them. A
+    // token that starts later than the previous digit but doesn't end after it is inside
+    // it, so the search moves on to the next start.
+    pub(crate) fn first_digits(&self, line: &str, count: usize) -> Vec<u32> {
+        let mut digits = Vec::<Match<u32>>::new();
+        let mut from = 0;
+        while digits.len() < count {
+            let Some(found) = self.matcher.first_from(line, from) else {
+                break;
+            };
+            from = found.start + 1;
+            if digits.last().is_none_or(|last| found.end > last.end) {
+                digits.push(found);
+            }
+        }
+        digits.into_iter().map(|found| found.value).collect()
+    }
+
+    // The last `count` digits of `digits_in`, in order, found from the end of the line.
+    pub(crate) fn last_digits(&self, line: &str, count: usize) -> Vec<u32> {
+        let mut digits = Vec::<Match<u32>>::new();
+        let mut to = line.len();
+        while digits.len() < count {
+            let Some(found) = self.matcher.last_before(line, to) else {
+                break;
+            };
+            to = found.end - 1;
+            if digits.last().is_none_or(|last| found.start < last.start) {
+                digits.push(found);
+            }
+        }
+        digits.into_iter().rev().map(|found| found.value).collect()
+    }
+
+    pub(crate) fn digit_matches(&self, line: &str) -> Vec<Match<u32>> {
+        let mut matches = self.all(line);
+        matches.sort_by_key(|found| (found.start, std::cmp::Reverse(found.end)));
+        let mut end = 0;
+        matches.into_iter()
+            .filter(|found| {
+                let outside = found.end > end;
+                end = end.max(found.end);
+                outside
+            })
+            .collect()
+    }
+
+    pub fn number(&self, line: &str) -> Option<u32> {
+        Some(self.first(line)?.value * 10 + self.last(line)?.value)
+    }
+}
+
+impl fmt::Debug for Vocabulary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Vocabulary").field(&self.tokens).finish()
+    }
+}
+
+// Adds a token with its digit, or returns what was expected of the token instead.
+fn add(tokens: &mut Vec<(String, u32)>, token: &str, value: u32) -> std::result::Result<(), String> {
+    if token.is_empty() {
+        return Err("a token".to_string());
+    }
+    match tokens.iter().find(|(existing, _)| existing == token) {
+        Some((_, existing)) if *existing != value => {
+            Err(format!("a token without another value than {}", existing))
+        }
+        Some(_) => Ok(()),
+        None => {
+            tokens.push((token.to_string(), value));
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_builtins() {
+        assert_eq!(Some(29), Vocabulary::english().number("two1nine"));
+        assert_eq!(Some(11), Vocabulary::digits().number("two1nine"));
+        assert_eq!(None, Vocabulary::digits().number("eightwothree"));
+
+        let french = Vocabulary::from_spec("digits+french").unwrap();
+        assert_eq!(Some(29), french.number("xdeux1neufz"));
+        assert_eq!(Some(86), french.number("huitroisix"));
+        assert_eq!(Some(3), Vocabulary::from_spec("zero+digits").unwrap().number("zero13"));
+        assert_eq!(Some(48), Vocabulary::from_spec("roman").unwrap().number("xIVxxVIII"));
+        assert_eq!(18, Vocabulary::english().tokens().len());
+    }
+
+    #[test]
+    fn test_digits_in() {
+        assert_eq!(vec![8, 2, 3], Vocabulary::english().digits_in("eightwothree"));
+        assert_eq!(vec![1, 4], Vocabulary::digits().digits_in("a1bfour4"));
+        assert_eq!(vec![8, 4, 9], Vocabulary::from_spec("roman").unwrap().digits_in("VIIIxIVxIX"));
+        assert!(Vocabulary::english().digits_in("abc").is_empty());
+    }
+
+    #[test]
+    fn test_first_and_last_digits() {
+        let roman = Vocabulary::from_spec("roman").unwrap();
+        let lines = [
+            (Vocabulary::english(), "eightwothree"),
+            (Vocabulary::english(), "xtwone3fourninee"),
+            (Vocabulary::english(), "oneightwoneight"),
+            (Vocabulary::english(), "7"),
+            (Vocabulary::english(), "abc"),
+            (&roman, "VIIIxIVxIXVIII"),
+            (&roman, "IIIVIIII"),
+        ];
+        for (vocabulary, line) in lines {
+            let digits = vocabulary.digits_in(line);
+            for count in 1..=4 {
+                let first = &digits[..count.min(digits.len())];
+                let last = &digits[digits.len() - count.min(digits.len())..];
+                assert_eq!(first, vocabulary.first_digits(line, count), "{} first {}", line, count);
+                assert_eq!(last, vocabulary.last_digits(line, count), "{} last {}", line, count);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let vocabulary = Vocabulary::parse("# Spanish\nuno = 1\n\n dos=2\nuno = 1\n").unwrap();
+        assert_eq!(vec![("uno".to_string(), 1), ("dos".to_string(), 2)], vocabulary.tokens());
+        assert_eq!(Some(12), vocabulary.number("xunodos"));
+
+        let error = Vocabulary::parse("uno = 1\ndos = 12\n").unwrap_err();
+        assert!(matches!(error, Error::Parse(error) if (error.line, error.column) == (2, 7) && error.text == "12"));
+        let error = Vocabulary::parse("uno = 1\nuno = 2\n").unwrap_err();
+        assert!(matches!(error, Error::Parse(error) if error.line == 2 && error.expected.contains("another value than 1")));
+        assert!(matches!(Vocabulary::parse(" = 1"), Err(Error::Parse(_))));
+        assert!(matches!(Vocabulary::parse("uno"), Err(Error::Parse(_))));
+    }
+
+    #[test]
+    fn test_from_spec() {
+        let dir = std::env::temp_dir().join(format!("aoc-01-vocabulary-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("tokens.txt");
+        std::fs::write(&path, "ten = 0\nein = 1\n").unwrap();
+
+        let vocabulary = Vocabulary::from_spec(&format!("digits+{}", path.display())).unwrap();
+        assert_eq!(Some(10), vocabulary.number("eintenx"));
+        assert!(matches!(Vocabulary::from_spec("digits+klingon"), Err(Error::Option { name, .. }) if name == OPTION));
+        assert!(matches!(Vocabulary::from_spec("digits+french+quatre.txt"), Err(Error::Option { .. })));
+        let error = |tokens: &[(&str, u32)]| match Vocabulary::new(tokens.iter().copied()) {
+            Err(Error::Parse(error)) => (error.text, error.expected),
+            result => panic!("Unexpected result {:?}", result),
+        };
+        assert_eq!(("one".to_string(), "a token without another value than 1".to_string()), error(&[("one", 1), ("one", 2)]));
+        assert!(matches!(Vocabulary::new([("one", 12)]), Err(Error::Option { name, reason }) if name == OPTION && reason.contains("12")));
+        assert_eq!((String::new(), "a token".to_string()), error(&[("", 1)]));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,134 @@
+pub mod part1;
+pub mod part2;
+
+use aoc_common::{parse_lines, Answer, Error, Input, Solution};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+
+    type Input = Vec<Game>;
+
+    fn parse(input: &Input) -> aoc_common::Result<Self::Input> {
+        parse_lines(input, Game::parse)
+    }
+
+    fn part1(input: &Self::Input) -> aoc_common::Result<Answer> {
+        Ok(part1::solve(input).into())
+    }
+
+    fn part2(input: &Self::Input) -> aoc_common::Result<Answer> {
+        Ok(part2::solve(input).into())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Game {
+    id: u32,
+    max_set: Set,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Set {
+    red: u32,
+    green: u32,
+    blue: u32,
+}
+
+impl Game {
+    fn parse(line: &str) -> aoc_common::Result<Self> {
+        let (game, sets) = line.split_once(": ")
+            .ok_or_else(|| Error::parse(line, &line[line.len()..], "': '"))?;
+        let id = game.strip_prefix("Game ")
+            .ok_or_else(|| Error::parse(line, game, "'Game <id>'"))?;
+        let id = id.parse::<u32>()
+            .map_err(|_| Error::parse(line, id, "a game id"))?;
+        let max_set = sets.split("; ")
+            .map(|description| Set::parse(line, description))
+            .try_fold(Set::default(), |max_set, set| set.map(|set| Set {
+                red: max_set.red.max(set.red),
+                green: max_set.green.max(set.green),
+                blue: max_set.blue.max(set.blue),
+            }))?;
+        Ok(Game {
+            id,
+            max_set,
+        })
+    }
+}
+
+impl Set {
+    fn parse(line: &str, description: &str) -> aoc_common::Result<Self> {
+        let mut set = Set::default();
+        for part in description.split(", ") {
+            let (count, color) = part.split_once(' ')
+                .ok_or_else(|| Error::parse(line, part, "'<count> <color>'"))?;
+            let count = count.parse::<u32>()
+                .map_err(|_| Error::parse(line, count, "a count"))?;
+            match color {
+                "red" => set.red = count,
+                "green" => set.green = count,
+                "blue" => set.blue = count,
+                bad_color => return Err(Error::parse(line, bad_color, "'red', 'green' or 'blue'")),
+            }
+        }
+        Ok(set)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_common::get_input_lines;
+    use super::*;
+
+    #[test]
+    fn test_get_input_lines() {
+        const LINES: [&str; 5] = [
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
+            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+        ];
+
+        assert_eq!(LINES, get_input_lines("./src/input/examples/example1.txt").unwrap().as_slice());
+    }
+
+    #[test]
+    fn test_game_from() {
+        const GAME: Game = Game {
+            id: 1,
+            max_set: Set { red: 4, green: 2, blue: 6 },
+        };
+
+        assert_eq!(GAME, Game::parse("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green").unwrap());
+    }
+
+    #[test]
+    fn test_set_from() {
+        assert_eq!(Set { red: 0, green: 2, blue: 0 }, Set::parse("2 green", "2 green").unwrap());
+        assert_eq!(Set { red: 1, green: 2, blue: 6 }, Set::parse("1 red, 2 green, 6 blue", "1 red, 2 green, 6 blue").unwrap());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |input: &str| match Day02::parse(&Input::from(input)) {
+            Err(Error::Parse(error)) => (error.line, error.column, error.text),
+            _ => panic!("Expected a parse error for '{}'", input),
+        };
+
+        assert_eq!((2, 19, "purple".to_string()), error("Game 1: 2 green\nGame 2: 3 blue, 4 purple"));
+        assert_eq!((1, 9, "x".to_string()), error("Game 1: x red"));
+        assert_eq!((1, 6, "one".to_string()), error("Game one: 3 red"));
+        assert_eq!((1, 1, "Round 1".to_string()), error("Round 1: 3 red"));
+        assert_eq!((1, 7, "".to_string()), error("Game 1"));
+        assert_eq!((1, 9, "3red".to_string()), error("Game 1: 3red"));
+    }
+}
+
+aoc_common::example_tests!(Day02);
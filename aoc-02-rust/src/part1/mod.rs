@@ -1,8 +1,7 @@
 use crate::Game;
 
-pub(crate) fn solve(lines: &[String]) -> u32 {
-    lines.iter()
-        .map(|line| Game::from(line.as_str()))
+pub fn solve(games: &[Game]) -> u32 {
+    games.iter()
         .filter(|game| {
             game.max_set.red <= 12
                 && game.max_set.green <= 13
@@ -14,18 +13,18 @@ pub(crate) fn solve(lines: &[String]) -> u32 {
 
 #[cfg(test)]
 mod test {
-    use super::*;
+    use aoc_common::{Answer, Input, Solution};
+    use crate::Day02;
 
     #[test]
     fn test_solve() {
-        let lines = [
-            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green".to_string(),
-            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue".to_string(),
-            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red".to_string(),
-            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red".to_string(),
-            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green".to_string(),
-        ];
+        let input = Day02::parse(&Input::from("\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green")).unwrap();
 
-        assert_eq!(8, solve(lines.as_ref()));
+        assert_eq!(Answer::Unsigned(8), Day02::part1(&input).unwrap());
     }
 }
@@ -0,0 +1,146 @@
+mod number;
+
+use std::collections::HashMap;
+use aoc_common::{Answer, Error, Input, Solution};
+use number::Number;
+use number::adjacency::{Adjacency, Gear};
+
+pub struct Day03;
+
+pub struct Schematic {
+    numbers: Vec<(Number, Adjacency)>,
+}
+
+#[derive(Clone, Copy)]
+struct Ratio {
+    value: u32,
+    count: u32,
+}
+
+impl Ratio {
+    fn multiply(self, number: Number) -> Self {
+        Self {
+            value: self.value * number.value,
+            count: self.count + 1,
+        }
+    }
+}
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+
+    type Input = Schematic;
+
+    fn parse(input: &Input) -> aoc_common::Result<Self::Input> {
+        check_width(input)?;
+        let grid = input.grid();
+        let mut numbers = Vec::new();
+
+        let mut on_number_end = |number: Number| {
+            let adjacency = number.get_adjacency(&grid);
+            numbers.push((number, adjacency));
+        };
+
+        for (line_index, line) in grid.rows().iter().enumerate() {
+            let mut current_number: Option<Number> = None;
+            for (char_index, char) in line.iter().copied().enumerate() {
+                if char.is_ascii_digit() {
+                    if let Some(n) = current_number {
+                        current_number = Some(n.adding_digit(char));
+                    } else {
+                        current_number = Some(Number::single_digit(line_index, char_index, char));
+                    }
+                } else if let Some(number) = current_number {
+                    on_number_end(number);
+                    current_number = None;
+                }
+            }
+            if let Some(number) = current_number {
+                on_number_end(number);
+            }
+        }
+
+        Ok(Schematic { numbers })
+    }
+
+    fn part1(input: &Self::Input) -> aoc_common::Result<Answer> {
+        Ok(input.part_numbers()
+            .map(|(number, _)| number.value)
+            .sum::<u32>()
+            .into())
+    }
+
+    fn part2(input: &Self::Input) -> aoc_common::Result<Answer> {
+        let mut ratio_by_gear = HashMap::<Gear, Ratio>::new();
+        for (number, adjacency) in input.part_numbers() {
+            if let Some(gear) = adjacency.gear {
+                let ratio = ratio_by_gear.get(&gear).unwrap_or(&Ratio { value: 1, count: 0 });
+                ratio_by_gear.insert(gear, ratio.multiply(*number));
+            }
+        }
+        Ok(ratio_by_gear.values()
+            .filter_map(|ratio|
+                if ratio.count == 2 {
+                   Some(ratio.value)
+                } else {
+                    None
+                })
+            .sum::<u32>()
+            .into())
+    }
+}
+
+// Numbers are compared with the rows above and below them, so every row has to be as wide
+// as the first one. The grid is indexed by byte, so widths are counted in bytes.
+fn check_width(input: &Input) -> aoc_common::Result<()> {
+    let width = input.lines().next().map_or(0, str::len);
+    let Some((index, line)) = input.lines().enumerate().find(|(_, line)| line.len() != width) else {
+        return Ok(());
+    };
+    let extra = line.get(width..).unwrap_or(&line[line.len()..]);
+    Err(Error::parse(line, extra, &format!("a row of {} bytes", width)).at_line(index + 1))
+}
+
+impl Schematic {
+    fn part_numbers(&self) -> impl Iterator<Item = &(Number, Adjacency)> {
+        self.numbers.iter()
+            .filter(|(_, adjacency)| adjacency.is_adjacent_to_a_symbol)
+    }
+}
+
+aoc_common::example_tests!(Day03);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let input = Day03::parse(&Input::read("./src/input/examples/example1.txt").unwrap()).unwrap();
+
+        assert_eq!(Answer::Unsigned(4361), Day03::part1(&input).unwrap());
+        assert_eq!(Answer::Unsigned(467835), Day03::part2(&input).unwrap());
+    }
+
+    #[test]
+    fn test_parse_ragged_rows() {
+        let Err(Error::Parse(error)) = Day03::parse(&Input::from("..12\n.*\n")) else {
+            panic!("a short row should be a parse error");
+        };
+        assert_eq!((2, 3, "", "a row of 4 bytes"), (error.line, error.column, error.text.as_str(), error.expected.as_str()));
+
+        let Err(Error::Parse(error)) = Day03::parse(&Input::from("..12\n.*..56\n")) else {
+            panic!("a long row should be a parse error");
+        };
+        assert_eq!((2, 5, "56"), (error.line, error.column, error.text.as_str()));
+
+        let Err(Error::Parse(error)) = Day03::parse(&Input::from("..12\n.é\n")) else {
+            panic!("a row of fewer bytes should be a parse error");
+        };
+        assert_eq!((2, "a row of 4 bytes"), (error.line, error.expected.as_str()));
+    }
+}
@@ -1,3 +1,4 @@
+use aoc_common::Grid;
 use super::Number;
 
 #[derive(Default, Debug, PartialEq)]
@@ -22,12 +23,14 @@ impl Adjacency {
 }
 
 impl Number {
-    fn get_adjacency_on_left_or_right_char(self, line: &str, char_index: usize) -> Adjacency {
-        let adjacent_char = line.as_bytes()[char_index] as char;
+    fn get_adjacency_on_left_or_right_char(self, line: &[u8], char_index: usize) -> Adjacency {
+        let Some(adjacent_char) = line.get(char_index).copied() else {
+            return Adjacency::default();
+        };
         Adjacency {
             is_adjacent_to_a_symbol: is_symbol(adjacent_char),
             gear:
-                if adjacent_char == '*' {
+                if adjacent_char == b'*' {
                     Some(Gear {
                         line_index: self.line_index,
                         char_index,
@@ -38,25 +41,25 @@ impl Number {
         }
     }
 
-    fn get_adjacency_for_upper_or_lower_line(self, line: &str, line_index: usize) -> Adjacency {
+    fn get_adjacency_for_upper_or_lower_line(self, line: &[u8], line_index: usize) -> Adjacency {
         let leftmost_char_index = if self.start_index > 0 {
             self.start_index - 1
         } else {
             self.start_index
         };
-        let rightmost_char_index = if self.end_index < line.len() - 1 {
+        let rightmost_char_index = if self.end_index + 1 < line.len() {
             self.end_index + 1
         } else {
             self.end_index
         };
-        let adjacent_chars = line.get(leftmost_char_index..=rightmost_char_index).unwrap();
+        let adjacent_chars = line.get(leftmost_char_index..=rightmost_char_index).unwrap_or_default();
         let mut is_adjacent_to_a_symbol = false;
         let mut gear_index = None;
-        for (offset, adjacent_char) in adjacent_chars.chars().enumerate() {
+        for (offset, adjacent_char) in adjacent_chars.iter().copied().enumerate() {
             if is_symbol(adjacent_char) {
                 is_adjacent_to_a_symbol = true;
             }
-            if adjacent_char == '*' {
+            if adjacent_char == b'*' {
                 gear_index = Some(Gear {
                     line_index,
                     char_index: leftmost_char_index + offset
@@ -70,29 +73,27 @@ impl Number {
         }
     }
 
-    pub(crate) fn get_adjacency(&self, lines: &[String]) -> Adjacency {
+    pub(crate) fn get_adjacency(&self, grid: &Grid) -> Adjacency {
         let mut adjacency = Adjacency::default();
-        let line = lines[self.line_index].as_str();
+        let line = grid.row(self.line_index).unwrap_or_default();
         if self.start_index > 0 {
             adjacency = adjacency.merge(self.get_adjacency_on_left_or_right_char(line, self.start_index - 1));
         }
-        if self.end_index < line.len() - 1 {
+        if self.end_index + 1 < line.len() {
             adjacency = adjacency.merge(self.get_adjacency_on_left_or_right_char(line, self.end_index + 1));
         }
-        if self.line_index > 0 {
-            let upper_line = lines[self.line_index - 1].as_str();
+        if let Some(upper_line) = self.line_index.checked_sub(1).and_then(|index| grid.row(index)) {
             adjacency = adjacency.merge(self.get_adjacency_for_upper_or_lower_line(upper_line, self.line_index - 1));
         }
-        if self.line_index < lines.len() - 1 {
-            let lower_line = lines[self.line_index + 1].as_str();
+        if let Some(lower_line) = grid.row(self.line_index + 1) {
             adjacency = adjacency.merge(self.get_adjacency_for_upper_or_lower_line(lower_line, self.line_index + 1));
         }
         adjacency
     }
 }
 
-fn is_symbol(c: char) -> bool {
-    !c.is_ascii_digit() && c != '.'
+fn is_symbol(c: u8) -> bool {
+    !c.is_ascii_digit() && c != b'.'
 }
 
 #[cfg(test)]
@@ -101,18 +102,19 @@ mod test {
 
     #[test]
     fn test_get_adjacency() {
-        let lines = &[
-            "467..114..".to_string(),
-            "...*......".to_string(),
-            "..35..633.".to_string(),
-            "......#...".to_string(),
-            "617*......".to_string(),
-            ".....+.58.".to_string(),
-            "..592.....".to_string(),
-            "......755.".to_string(),
-            "...$.*....".to_string(),
-            ".664.598..".to_string(),
-        ];
+        let input = aoc_common::Input::from("\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+");
+        let lines = &input.grid();
         const NUMBER_467: Number = Number { value: 467, line_index: 0, start_index: 0, end_index: 2 };
         const ADJACENCY_467: Adjacency = Adjacency { is_adjacent_to_a_symbol: true, gear: Some(Gear { line_index: 1, char_index: 3 }) };
         assert_eq!(ADJACENCY_467, NUMBER_467.get_adjacency(lines));
@@ -9,18 +9,18 @@ pub(crate) struct Number {
 }
 
 impl Number {
-    pub(crate) fn single_digit(line_index: usize, start_index: usize, digit_char: char) -> Self {
+    pub(crate) fn single_digit(line_index: usize, start_index: usize, digit_char: u8) -> Self {
         Self {
-            value: digit_char.to_digit(10).unwrap(),
+            value: (digit_char - b'0') as u32,
             line_index,
             start_index,
             end_index: start_index,
         }
     }
 
-    pub(crate) fn adding_digit(self, digit_char: char) -> Self {
+    pub(crate) fn adding_digit(self, digit_char: u8) -> Self {
         Self {
-            value: self.value * 10 + digit_char.to_digit(10).unwrap(),
+            value: self.value * 10 + (digit_char - b'0') as u32,
             end_index: self.end_index + 1,
             ..self
         }
@@ -0,0 +1,4 @@
+fn main() {
+    // Part 2 isn't solved yet, so its example is checked only with --ignored.
+    aoc_common::example::generate_tests_ignoring(&[2]);
+}
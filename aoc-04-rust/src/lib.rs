@@ -0,0 +1,91 @@
+use std::collections::HashSet;
+use std::io::BufRead;
+use aoc_common::{parse_lines, parse_numbers_in, stream_lines, Answer, Error, Input, Solution};
+
+pub struct Day04;
+
+pub struct Card {
+    winning_numbers: HashSet<u32>,
+    my_numbers: HashSet<u32>,
+}
+
+impl Card {
+    fn parse(line: &str) -> aoc_common::Result<Self> {
+        let end = &line[line.len()..];
+        let (_, numbers) = line.split_once(':').ok_or_else(|| Error::parse(line, end, "':'"))?;
+        let (winning_numbers, my_numbers) = numbers.split_once('|').ok_or_else(|| Error::parse(line, end, "'|'"))?;
+
+        Ok(Card {
+            winning_numbers: parse_numbers_in(line, winning_numbers)?,
+            my_numbers: parse_numbers_in(line, my_numbers)?,
+        })
+    }
+
+    fn points(&self) -> u32 {
+        let count_winning_numbers = self.winning_numbers
+            .intersection(&self.my_numbers)
+            .collect::<Vec<_>>()
+            .len() as u32;
+
+        if count_winning_numbers == 0 {
+            0
+        } else {
+            2u32.pow(count_winning_numbers - 1)
+        }
+    }
+}
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+
+    type Input = Vec<Card>;
+
+    fn parse(input: &Input) -> aoc_common::Result<Self::Input> {
+        parse_lines(input, Card::parse)
+    }
+
+    fn part1(input: &Self::Input) -> aoc_common::Result<Answer> {
+        Ok(input.iter()
+            .map(Card::points)
+            .sum::<u32>()
+            .into())
+    }
+
+    fn part2(_: &Self::Input) -> aoc_common::Result<Answer> {
+        Err(Error::Unsolved { day: Self::DAY, part: 2 })
+    }
+
+    fn stream(reader: &mut dyn BufRead, parts: &[u8]) -> aoc_common::Result<Vec<Answer>> {
+        if let Some(part) = parts.iter().find(|part| **part != 1) {
+            return Err(Error::Unsolved { day: Self::DAY, part: *part });
+        }
+        let mut points = 0;
+        for (index, line) in stream_lines(reader).enumerate() {
+            points += Card::parse(&line?).map_err(|error| error.at_line(index + 1))?.points();
+        }
+        Ok(parts.iter().map(|_| points.into()).collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |input: &str| match Day04::parse(&Input::from(input)) {
+            Err(Error::Parse(error)) => (error.line, error.column, error.text, error.expected),
+            _ => panic!("Expected a parse error for '{}'", input),
+        };
+
+        assert_eq!((2, 18, "".to_string(), "':'".to_string()), error("Card 1: 41 | 83\nCard 2 41 48 | 83"));
+        assert_eq!((1, 20, "".to_string(), "'|'".to_string()), error("Card 1: 41 48 83 86"));
+        assert_eq!((1, 12, "x".to_string(), "a number".to_string()), error("Card 1: 41 x | 83"));
+    }
+}
+
+aoc_common::example_tests!(Day04);
@@ -0,0 +1,127 @@
+use std::fmt;
+use std::str::FromStr;
+use crate::big::BigUint;
+
+#[derive(Clone, Debug)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Big(BigUint),
+    Text(String),
+}
+
+impl Answer {
+    fn is_numeric(&self) -> bool {
+        !matches!(self, Answer::Text(_))
+    }
+}
+
+// Numeric answers are equal when they hold the same value, whatever their variant.
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            (a, b) if a.is_numeric() && b.is_numeric() => a.to_string() == b.to_string(),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Unsigned(value) => write!(f, "{}", value),
+            Answer::Signed(value) => write!(f, "{}", value),
+            Answer::Big(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = std::convert::Infallible;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        Ok(if let Ok(value) = string.parse::<u64>() {
+            Answer::Unsigned(value)
+        } else if let Ok(value) = string.parse::<i64>() {
+            Answer::Signed(value)
+        } else if let Ok(value) = string.parse::<BigUint>() {
+            Answer::Big(value)
+        } else {
+            Answer::Text(string.to_string())
+        })
+    }
+}
+
+macro_rules! impl_from_unsigned {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(value: $t) -> Self {
+                Answer::Unsigned(value as u64)
+            }
+        })*
+    };
+}
+
+macro_rules! impl_from_signed {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(value: $t) -> Self {
+                Answer::Signed(value as i64)
+            }
+        })*
+    };
+}
+
+impl_from_unsigned!(u8, u16, u32, u64, usize);
+impl_from_signed!(i8, i16, i32, i64, isize);
+
+impl From<BigUint> for Answer {
+    fn from(value: BigUint) -> Self {
+        Answer::Big(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!("142", Answer::from(142u32).to_string());
+        assert_eq!("-3", Answer::from(-3i64).to_string());
+        assert_eq!("EZPZ", Answer::from("EZPZ").to_string());
+    }
+
+    #[test]
+    fn test_eq() {
+        assert_eq!(Answer::Unsigned(281), Answer::Signed(281));
+        assert_eq!(Answer::Unsigned(281), Answer::Big(BigUint::from(281)));
+        assert_ne!(Answer::Unsigned(281), Answer::Signed(-281));
+        assert_ne!(Answer::Unsigned(281), Answer::Text("281".to_string()));
+        assert_eq!(Answer::from("abc"), Answer::from("abc".to_string()));
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert!(matches!("2286".parse::<Answer>().unwrap(), Answer::Unsigned(2286)));
+        assert!(matches!("-12".parse::<Answer>().unwrap(), Answer::Signed(-12)));
+        assert!(matches!("123456789012345678901234567890".parse::<Answer>().unwrap(), Answer::Big(_)));
+        assert!(matches!("LGHFPEPA".parse::<Answer>().unwrap(), Answer::Text(_)));
+    }
+}
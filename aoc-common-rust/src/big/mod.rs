@@ -0,0 +1,135 @@
+use std::fmt;
+use std::str::FromStr;
+
+const BASE: u64 = 1_000_000_000;
+const BASE_DIGITS: usize = 9;
+
+// Little-endian limbs in base 10^9, without trailing zero limbs.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        self.limbs.iter().rev()
+            .try_fold(0u64, |value, limb| value.checked_mul(BASE)?.checked_add(*limb as u64))
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        let mut limbs = Vec::with_capacity(self.limbs.len().max(other.limbs.len()) + 1);
+        let mut carry = 0;
+        for index in 0..self.limbs.len().max(other.limbs.len()) {
+            let sum = *self.limbs.get(index).unwrap_or(&0) as u64
+                + *other.limbs.get(index).unwrap_or(&0) as u64
+                + carry;
+            limbs.push((sum % BASE) as u32);
+            carry = sum / BASE;
+        }
+        if carry > 0 {
+            limbs.push(carry as u32);
+        }
+        Self { limbs }
+    }
+
+    pub fn mul_small(&self, factor: u32) -> Self {
+        let mut limbs = Vec::with_capacity(self.limbs.len() + 1);
+        let mut carry = 0;
+        for limb in &self.limbs {
+            let product = *limb as u64 * factor as u64 + carry;
+            limbs.push((product % BASE) as u32);
+            carry = product / BASE;
+        }
+        while carry > 0 {
+            limbs.push((carry % BASE) as u32);
+            carry /= BASE;
+        }
+        Self { limbs }.normalized()
+    }
+
+    fn normalized(mut self) -> Self {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        self
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(mut value: u64) -> Self {
+        let mut limbs = Vec::new();
+        while value > 0 {
+            limbs.push((value % BASE) as u32);
+            value /= BASE;
+        }
+        Self { limbs }
+    }
+}
+
+impl FromStr for BigUint {
+    type Err = ();
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        if string.is_empty() || !string.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(());
+        }
+        let limbs = string.as_bytes()
+            .rchunks(BASE_DIGITS)
+            .map(|chunk| chunk.iter().fold(0, |limb, digit| limb * 10 + (digit - b'0') as u32))
+            .collect();
+        Ok(Self { limbs }.normalized())
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.limbs.split_last() {
+            None => write!(f, "0"),
+            Some((most_significant, rest)) => {
+                write!(f, "{}", most_significant)?;
+                for limb in rest.iter().rev() {
+                    write!(f, "{:09}", limb)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_str_and_display() {
+        for string in ["0", "7", "1000000000", "123456789012345678901234567890"] {
+            assert_eq!(string, string.parse::<BigUint>().unwrap().to_string());
+        }
+        assert_eq!("12", "00012".parse::<BigUint>().unwrap().to_string());
+        assert!("".parse::<BigUint>().is_err());
+        assert!("-1".parse::<BigUint>().is_err());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let big = BigUint::from(u64::MAX);
+        assert_eq!("36893488147419103230", big.add(&big).to_string());
+        assert_eq!("184467440737095516150", big.mul_small(10).to_string());
+        assert!(BigUint::from(12).mul_small(0).is_zero());
+    }
+
+    #[test]
+    fn test_to_u64() {
+        assert_eq!(Some(u64::MAX), BigUint::from(u64::MAX).to_u64());
+        assert_eq!(None, BigUint::from(u64::MAX).mul_small(2).to_u64());
+        assert_eq!(Some(0), BigUint::zero().to_u64());
+    }
+}
@@ -0,0 +1,145 @@
+use std::fmt;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub enum Error {
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    Read(std::io::Error),
+    Parse(ParseError),
+    Unsolved {
+        day: u8,
+        part: u8,
+    },
+    Unexplained {
+        day: u8,
+    },
+    InputNotFound {
+        day: u8,
+        tried: Vec<PathBuf>,
+    },
+    Key(String),
+    Option {
+        name: String,
+        reason: String,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub expected: String,
+    pub source_line: String,
+}
+
+impl ParseError {
+    // `token` is expected to be a slice of `source_line`, so that its column can be found.
+    pub fn new(source_line: &str, token: &str, expected: &str) -> Self {
+        let start = source_line.as_ptr() as usize;
+        let position = token.as_ptr() as usize;
+        let offset = if position >= start && position + token.len() <= start + source_line.len() {
+            position - start
+        } else {
+            0
+        };
+        Self {
+            line: 1,
+            column: source_line[..offset].chars().count() + 1,
+            text: token.to_string(),
+            expected: expected.to_string(),
+            source_line: source_line.to_string(),
+        }
+    }
+}
+
+impl Error {
+    pub fn parse(source_line: &str, token: &str, expected: &str) -> Self {
+        Error::Parse(ParseError::new(source_line, token, expected))
+    }
+
+    pub fn at_line(self, line: usize) -> Self {
+        match self {
+            Error::Parse(error) => Error::Parse(ParseError { line, ..error }),
+            error => error,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Line {}, column {}: expected {}, ", self.line, self.column, self.expected)?;
+        if self.text.is_empty() {
+            writeln!(f, "found nothing")?;
+        } else {
+            writeln!(f, "found '{}'", self.text)?;
+        }
+        writeln!(f, "    {}", self.source_line)?;
+        write!(f, "    {}{}", " ".repeat(self.column - 1), "^".repeat(self.text.chars().count().max(1)))
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => write!(f, "The input '{}' couldn't be read: {}", path.display(), source),
+            Error::Read(source) => write!(f, "The input couldn't be read: {}", source),
+            Error::Parse(error) => write!(f, "{}", error),
+            Error::Unsolved { day, part } => write!(f, "Day {} part {} isn't solved yet", day, part),
+            Error::Unexplained { day } => write!(f, "Day {} has no explain mode", day),
+            Error::InputNotFound { day, tried } => {
+                write!(f, "No input found for day {}, tried:", day)?;
+                for path in tried {
+                    write!(f, "\n    {}", path.display())?;
+                }
+                Ok(())
+            }
+            Error::Key(message) => write!(f, "{}", message),
+            Error::Option { name, reason } => write!(f, "Bad option '{}': {}", name, reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } | Error::Read(source) => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_column() {
+        const LINE: &str = "Game 3: 8 purple, 6 blue";
+
+        let error = ParseError::new(LINE, &LINE[10..16], "a color");
+        assert_eq!((1, 11, "purple"), (error.line, error.column, error.text.as_str()));
+
+        let error = ParseError::new(LINE, "elsewhere", "a color");
+        assert_eq!(1, error.column);
+    }
+
+    #[test]
+    fn test_display() {
+        const LINE: &str = "Game 3: 8 purple";
+
+        assert_eq!(
+            "Line 3, column 11: expected a color, found 'purple'\n    Game 3: 8 purple\n              ^^^^^^",
+            Error::parse(LINE, &LINE[10..], "a color").at_line(3).to_string()
+        );
+        assert_eq!(
+            "Line 1, column 17: expected ':', found nothing\n    Game 3: 8 purple\n                    ^",
+            Error::parse(LINE, &LINE[16..], "':'").to_string()
+        );
+    }
+}
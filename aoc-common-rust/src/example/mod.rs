@@ -0,0 +1,221 @@
+use std::path::{Path, PathBuf};
+use crate::{Answer, Error, Input, Result, Solution};
+
+pub const DIR: &str = "examples";
+
+const INPUT_EXTENSION: &str = "txt";
+const EXPECTED_EXTENSION: &str = "expected";
+
+pub fn dir(manifest_dir: &str) -> PathBuf {
+    Path::new(manifest_dir).join("src").join("input").join(DIR)
+}
+
+// Appended rather than set with `with_extension`, which would cut a dotted name short.
+fn file_path(dir: &Path, name: &str, extension: &str) -> PathBuf {
+    dir.join(format!("{}.{}", name, extension))
+}
+
+// An example from the puzzle text: '<name>.txt' holds its input and '<name>.expected'
+// the answers it's known to give, one 'partP = answer' line per part.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub expected: Vec<(u8, Answer)>,
+}
+
+impl Example {
+    pub fn load_all(dir: &Path) -> Result<Vec<Self>> {
+        let io_error = |source| Error::Io { path: dir.to_path_buf(), source };
+        let mut examples = Vec::new();
+        for entry in std::fs::read_dir(dir).map_err(io_error)? {
+            let path = entry.map_err(io_error)?.path();
+            if path.extension().is_some_and(|extension| extension == EXPECTED_EXTENSION) {
+                let name = path.file_stem().unwrap_or_default().to_string_lossy();
+                examples.push(Self::load(&file_path(dir, &name, INPUT_EXTENSION))?);
+            }
+        }
+        examples.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(examples)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let name = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+        let expected = crate::read_input(file_path(path.parent().unwrap_or(Path::new("")), &name, EXPECTED_EXTENSION))?;
+        Ok(Self {
+            name,
+            input: crate::read_input(path)?,
+            expected: parse_expected(&expected)?,
+        })
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<Vec<PathBuf>> {
+        let write = |extension: &str, contents: &str| {
+            let path = file_path(dir, &self.name, extension);
+            std::fs::write(&path, contents)
+                .map(|_| path.clone())
+                .map_err(|source| Error::Io { path, source })
+        };
+        std::fs::create_dir_all(dir).map_err(|source| Error::Io { path: dir.to_path_buf(), source })?;
+        Ok(vec![
+            write(INPUT_EXTENSION, &self.input)?,
+            write(EXPECTED_EXTENSION, &expected_text(&self.expected))?,
+        ])
+    }
+}
+
+// Called from a day's build script: writes one test per example and part into
+// $OUT_DIR/examples.rs, for `example_tests!` to include.
+pub fn generate_tests() {
+    generate_tests_ignoring(&[])
+}
+
+// The same, for a day with unsolved parts: their tests are generated ignored, so that
+// they still fail under `cargo test -- --ignored` until the part is solved.
+pub fn generate_tests_ignoring(unsolved: &[u8]) {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR isn't set");
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR isn't set");
+    let dir = dir(&manifest_dir);
+    // Watching the whole input directory also catches the examples directory being created.
+    println!("cargo:rerun-if-changed={}", dir.parent().unwrap_or(&dir).display());
+    let mut tests = String::new();
+    if dir.is_dir() {
+        for example in Example::load_all(&dir).unwrap_or_else(|error| panic!("{}", error)) {
+            let path = file_path(&dir, &example.name, INPUT_EXTENSION);
+            tests.push_str(&test_source(&example, &path, unsolved));
+        }
+    }
+    let path = Path::new(&out_dir).join("examples.rs");
+    std::fs::write(&path, tests).unwrap_or_else(|error| panic!("Couldn't write {}: {}", path.display(), error));
+}
+
+fn test_source(example: &Example, path: &Path, unsolved: &[u8]) -> String {
+    example.expected.iter()
+        .map(|(part, _)| {
+            let ignore = if unsolved.contains(part) {
+                format!("#[ignore = \"part {} isn't solved yet\"]\n", part)
+            } else {
+                String::new()
+            };
+            format!(
+                "#[test]\n{}fn {}_part{}() {{\n    aoc_common::example::check::<Day>({:?}, {});\n}}\n\n",
+                ignore, test_name(&example.name), part, path.display().to_string(), part
+            )
+        })
+        .collect()
+}
+
+fn test_name(name: &str) -> String {
+    let name = name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect::<String>();
+    if name.starts_with(|c: char| c.is_ascii_lowercase()) {
+        name
+    } else {
+        format!("example_{}", name)
+    }
+}
+
+// Solves one part of an example and compares it with its expected answer. A part that
+// isn't solved yet fails like any other error.
+pub fn check<S: Solution>(path: &str, part: u8) {
+    let example = Example::load(Path::new(path)).unwrap_or_else(|error| panic!("{}", error));
+    let expected = example.expected.iter()
+        .find(|(expected_part, _)| *expected_part == part)
+        .map(|(_, answer)| answer)
+        .unwrap_or_else(|| panic!("{} has no expected answer for part {}", example.name, part));
+    let input = S::parse(&Input::from(example.input.as_str())).unwrap_or_else(|error| panic!("{}: {}", example.name, error));
+    let answer = S::solve(&input, part).unwrap_or_else(|error| panic!("{} part {}: {}", example.name, part, error));
+    assert_eq!(*expected, answer, "{} part {}", example.name, part);
+}
+
+#[macro_export]
+macro_rules! example_tests {
+    ($solution:ty) => {
+        #[cfg(test)]
+        mod examples {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #[allow(dead_code)]
+            type Day = $solution;
+
+            include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+        }
+    };
+}
+
+pub fn parse_expected(text: &str) -> Result<Vec<(u8, Answer)>> {
+    crate::parse_lines(text, |line| {
+        let (key, answer) = line.split_once('=')
+            .ok_or_else(|| Error::parse(line, line, "'partP = answer'"))?;
+        let part = match key.trim() {
+            "part1" => 1,
+            "part2" => 2,
+            _ => return Err(Error::parse(line, key.trim(), "'part1' or 'part2'")),
+        };
+        let Ok(answer) = answer.trim().parse::<Answer>();
+        Ok((part, answer))
+    })
+}
+
+fn expected_text(expected: &[(u8, Answer)]) -> String {
+    expected.iter()
+        .map(|(part, answer)| format!("part{} = {}\n", part, answer))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_expected() {
+        let expected = vec![(1, Answer::Unsigned(142)), (2, Answer::Text("abc".to_string()))];
+        assert_eq!(expected, parse_expected("part1 = 142\npart2 = abc\n").unwrap());
+        assert_eq!("part1 = 142\npart2 = abc\n", expected_text(&expected));
+        match parse_expected("part1 = 142\npart3 = 1") {
+            Err(Error::Parse(error)) => assert_eq!((2, 1), (error.line, error.column)),
+            result => panic!("Unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_test_name() {
+        assert_eq!("example1", test_name("example1"));
+        assert_eq!("larger_grid", test_name("Larger-Grid"));
+        assert_eq!("example_2", test_name("2"));
+    }
+
+    #[test]
+    fn test_test_source() {
+        let example = Example {
+            name: "example1".to_string(),
+            input: String::new(),
+            expected: vec![(1, Answer::Unsigned(13)), (2, Answer::Unsigned(30))],
+        };
+        let source = test_source(&example, Path::new("/day/example1.txt"), &[2]);
+        assert_eq!(
+            "#[test]\nfn example1_part1() {\n    aoc_common::example::check::<Day>(\"/day/example1.txt\", 1);\n}\n\n\
+             #[test]\n#[ignore = \"part 2 isn't solved yet\"]\nfn example1_part2() {\n    aoc_common::example::check::<Day>(\"/day/example1.txt\", 2);\n}\n\n",
+            source
+        );
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = std::env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let examples = vec![
+            Example { name: "example1".to_string(), input: "1abc2\n".to_string(), expected: vec![(1, Answer::Unsigned(3))] },
+            Example { name: "example2".to_string(), input: "two1\n".to_string(), expected: vec![(2, Answer::Unsigned(21))] },
+            Example { name: "example2.v2".to_string(), input: "one\n".to_string(), expected: vec![(2, Answer::Unsigned(11))] },
+        ];
+        for example in &examples {
+            example.save(&dir).unwrap();
+        }
+        assert_eq!(examples, Example::load_all(&dir).unwrap());
+        assert!(dir.join("example2.v2.txt").is_file());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,23 @@
+use std::fs::File;
+use memmap2::Mmap;
+
+// A read-only mapping of a whole file, checked to be UTF-8 when it's made.
+pub(crate) struct Mapped(Mmap);
+
+impl Mapped {
+    pub(crate) fn new(file: &File) -> std::io::Result<Self> {
+        // SAFETY: a mapping is only sound while nothing else writes to or truncates the file.
+        // A write would change bytes already checked to be UTF-8 under `as_str`, and reading
+        // past a truncation raises SIGBUS. Mapped inputs are generated once and then only read,
+        // which `Input::map` requires of its callers.
+        let map = unsafe { Mmap::map(file)? };
+        std::str::from_utf8(&map).map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))?;
+        Ok(Self(map))
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        // SAFETY: the bytes were checked to be UTF-8 in `new` and, as long as the file is left
+        // alone while it's mapped, they haven't changed since.
+        unsafe { std::str::from_utf8_unchecked(&self.0) }
+    }
+}
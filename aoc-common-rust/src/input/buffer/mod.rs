@@ -0,0 +1,165 @@
+#[cfg(any(unix, windows))]
+mod map;
+
+use std::fmt;
+use std::ops::Deref;
+use std::path::Path;
+use std::sync::Arc;
+use crate::{Error, Result};
+
+enum Buffer {
+    Owned(String),
+    #[cfg(any(unix, windows))]
+    Mapped(map::Mapped),
+}
+
+// A puzzle input held in a single buffer, that lines, byte rows and grids borrow from.
+// Clones share the buffer, so a parsed input can keep it around without copying it.
+#[derive(Clone)]
+pub struct Input(Arc<Buffer>);
+
+impl Input {
+    pub fn read(path: impl AsRef<Path>) -> Result<Self> {
+        super::read_input(path).map(Self::from)
+    }
+
+    // Maps the file rather than reading it, for large generated inputs. Stdin, sealed
+    // inputs and platforms without mmap fall back to reading. The file must not be written
+    // to or truncated while the input, or any clone of it, is alive.
+    pub fn map(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if super::is_stdin(path) || super::seal::is_sealed(path) {
+            return Self::read(path);
+        }
+        Self::map_file(path)
+    }
+
+    #[cfg(any(unix, windows))]
+    fn map_file(path: &Path) -> Result<Self> {
+        let io_error = |source| Error::Io { path: path.to_path_buf(), source };
+        let file = std::fs::File::open(path).map_err(io_error)?;
+        let mapped = map::Mapped::new(&file).map_err(io_error)?;
+        Ok(Self(Arc::new(Buffer::Mapped(mapped))))
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    fn map_file(path: &Path) -> Result<Self> {
+        Self::read(path)
+    }
+
+    pub fn is_mapped(&self) -> bool {
+        !matches!(*self.0, Buffer::Owned(_))
+    }
+
+    pub fn as_str(&self) -> &str {
+        match &*self.0 {
+            Buffer::Owned(text) => text,
+            #[cfg(any(unix, windows))]
+            Buffer::Mapped(mapped) => mapped.as_str(),
+        }
+    }
+
+    // The same lines as `split_lines`, borrowed rather than copied.
+    pub fn lines(&self) -> std::str::Lines<'_> {
+        self.as_str().trim_end().lines()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[u8]> {
+        self.lines().map(str::as_bytes)
+    }
+
+    pub fn grid(&self) -> Grid<'_> {
+        Grid { rows: self.rows().collect() }
+    }
+}
+
+impl Deref for Input {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl From<String> for Input {
+    fn from(text: String) -> Self {
+        Self(Arc::new(Buffer::Owned(text)))
+    }
+}
+
+impl From<&str> for Input {
+    fn from(text: &str) -> Self {
+        Self::from(text.to_string())
+    }
+}
+
+impl fmt::Debug for Input {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Input").field(&self.as_str()).finish()
+    }
+}
+
+// The input's rows as bytes, indexed by row then column.
+#[derive(Debug, PartialEq)]
+pub struct Grid<'a> {
+    rows: Vec<&'a [u8]>,
+}
+
+impl<'a> Grid<'a> {
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn rows(&self) -> &[&'a [u8]] {
+        &self.rows
+    }
+
+    pub fn row(&self, row: usize) -> Option<&'a [u8]> {
+        self.rows.get(row).copied()
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> Option<u8> {
+        self.row(row)?.get(column).copied()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        let input = Input::from("ab\n\ncd\r\n\n");
+        assert_eq!(super::super::split_lines(&input), input.lines().collect::<Vec<_>>());
+        assert_eq!(vec![b"ab".as_slice(), b"", b"cd"], input.rows().collect::<Vec<_>>());
+        assert_eq!(0, Input::from("").lines().count());
+    }
+
+    #[test]
+    fn test_grid() {
+        let input = Input::from("467.\n..*.\n");
+        let grid = input.grid();
+        assert_eq!(2, grid.height());
+        assert_eq!(Some(b"..*.".as_slice()), grid.row(1));
+        assert_eq!(Some(b'*'), grid.get(1, 2));
+        assert_eq!(None, grid.get(1, 4));
+        assert_eq!(None, grid.get(2, 0));
+    }
+
+    #[test]
+    fn test_map() {
+        let input = Input::map("./src/input/test/test_input.txt").unwrap();
+        assert_eq!(cfg!(any(unix, windows)), input.is_mapped());
+        assert_eq!(Input::read("./src/input/test/test_input.txt").unwrap().as_str(), input.as_str());
+        assert_eq!(Some("treb7uchet"), input.clone().lines().next_back());
+
+        let dir = std::env::temp_dir().join(format!("aoc-buffer-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("empty.txt"), "").unwrap();
+        std::fs::write(dir.join("binary.txt"), [0xff, 0xfe]).unwrap();
+        assert_eq!("", Input::map(dir.join("empty.txt")).unwrap().as_str());
+        assert!(matches!(Input::map(dir.join("binary.txt")), Err(Error::Io { .. })));
+        assert!(matches!(Input::map(dir.join("missing.txt")), Err(Error::Io { .. })));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
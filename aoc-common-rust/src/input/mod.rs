@@ -0,0 +1,137 @@
+pub mod buffer;
+pub mod hash;
+pub mod resolve;
+pub mod seal;
+
+use std::collections::VecDeque;
+use std::io::{BufRead, Read};
+use std::path::Path;
+use crate::{Error, Result};
+
+pub const STDIN: &str = "-";
+
+pub fn is_stdin(path: &Path) -> bool {
+    path == Path::new(STDIN)
+}
+
+pub fn read_input(path: impl AsRef<Path>) -> Result<String> {
+    let path = path.as_ref();
+    if is_stdin(path) {
+        return read_input_from(std::io::stdin().lock());
+    }
+    if seal::is_sealed(path) {
+        return seal::read_sealed(path);
+    }
+    std::fs::read_to_string(path)
+        .map_err(|source| Error::Io {
+            path: path.to_path_buf(),
+            source,
+        })
+}
+
+pub fn read_input_from(mut reader: impl Read) -> Result<String> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input).map_err(Error::Read)?;
+    Ok(input)
+}
+
+// Yields the same lines as `split_lines`, without holding more than the current line
+// and the blank lines preceding it in memory.
+pub fn stream_lines<R: BufRead>(reader: R) -> Lines<R> {
+    Lines {
+        lines: reader.lines(),
+        blank: Vec::new(),
+        ready: VecDeque::new(),
+    }
+}
+
+pub struct Lines<R> {
+    lines: std::io::Lines<R>,
+    blank: Vec<String>,
+    ready: VecDeque<String>,
+}
+
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.ready.is_empty() {
+            match self.lines.next()? {
+                Err(error) => return Some(Err(Error::Read(error))),
+                Ok(line) if line.trim_end().is_empty() => self.blank.push(line),
+                Ok(line) => {
+                    self.ready.extend(self.blank.drain(..));
+                    self.ready.push_back(line);
+                }
+            }
+        }
+        self.ready.pop_front().map(Ok)
+    }
+}
+
+pub fn get_input_lines(path: impl AsRef<Path>) -> Result<Vec<String>> {
+    read_input(path).map(|input| split_lines(&input))
+}
+
+pub fn split_lines(input: &str) -> Vec<String> {
+    input.trim_end()
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+pub fn split_grid(input: &str) -> Vec<Vec<char>> {
+    input.trim_end()
+        .lines()
+        .map(|line| line.chars().collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_input_lines() {
+        const LINES: [&str; 3] = [
+            "1abc2",
+            "pqr3stu8vwx",
+            "treb7uchet",
+        ];
+
+        assert_eq!(LINES, get_input_lines("./src/input/test/test_input.txt").unwrap().as_slice());
+    }
+
+    #[test]
+    fn test_get_input_lines_missing_file() {
+        let error = get_input_lines("./src/input/test/missing.txt").unwrap_err();
+        assert!(matches!(error, Error::Io { .. }));
+        assert!(error.to_string().contains("missing.txt"));
+    }
+
+    #[test]
+    fn test_split_lines() {
+        assert_eq!(vec!["ab", "", "cd"], split_lines("ab\n\ncd\n\n"));
+        assert!(split_lines("").is_empty());
+    }
+
+    #[test]
+    fn test_read_input_from() {
+        assert_eq!("1abc2\n", read_input_from("1abc2\n".as_bytes()).unwrap());
+    }
+
+    #[test]
+    fn test_stream_lines() {
+        for input in ["ab\n\n  \ncd\n\n \n", "ab\r\n\r\n  \r\ncd", "", "\n\n"] {
+            let lines = stream_lines(input.as_bytes())
+                .collect::<Result<Vec<_>>>()
+                .unwrap();
+            assert_eq!(split_lines(input), lines);
+        }
+    }
+
+    #[test]
+    fn test_split_grid() {
+        assert_eq!(vec![vec!['4', '6'], vec!['.', '*']], split_grid("46\n.*\n"));
+    }
+}
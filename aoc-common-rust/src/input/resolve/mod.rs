@@ -0,0 +1,128 @@
+use std::path::{Path, PathBuf};
+use crate::{Error, Result};
+use super::is_stdin;
+
+pub const YEAR: u16 = 2023;
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+#[derive(Debug, Default)]
+pub struct Resolver {
+    pub flag: Option<PathBuf>,
+    pub input_dir: Option<PathBuf>,
+    pub cache_dir: Option<PathBuf>,
+    pub manifest_dir: Option<PathBuf>,
+}
+
+impl Resolver {
+    pub fn from_env(flag: Option<&Path>, manifest_dir: &str) -> Self {
+        Self {
+            flag: flag.map(Path::to_path_buf),
+            input_dir: std::env::var_os(INPUT_DIR_VAR).map(PathBuf::from),
+            cache_dir: cache_dir(),
+            manifest_dir: Some(PathBuf::from(manifest_dir)),
+        }
+    }
+
+    pub fn candidates(&self, day: u8) -> Vec<PathBuf> {
+        // An explicit path is never second-guessed.
+        if let Some(flag) = &self.flag {
+            return vec![flag.clone()];
+        }
+        [
+            self.input_dir.as_ref().map(|dir| dir.join(file_name(day))),
+            self.cache_dir.as_ref().map(|dir| dir.join(file_name(day))),
+            self.manifest_dir.as_ref().map(|dir| dir.join("src").join("input").join("input.txt")),
+            self.manifest_dir.as_ref().map(|dir| super::seal::sealed_path(&dir.join("src").join("input").join("input.txt"))),
+        ]
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    pub fn resolve(&self, day: u8) -> Result<PathBuf> {
+        let tried = self.candidates(day);
+        tried.iter()
+            .find(|path| is_stdin(path) || path.is_file())
+            .cloned()
+            .ok_or(Error::InputNotFound { day, tried })
+    }
+}
+
+pub fn file_name(day: u8) -> String {
+    format!("day{:02}.txt", day)
+}
+
+pub fn cache_dir() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("LOCALAPPDATA").map(PathBuf::from))
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+    Some(base.join("aoc").join(YEAR.to_string()))
+}
+
+pub fn config_dir() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join("aoc"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_candidates() {
+        let resolver = Resolver {
+            flag: None,
+            input_dir: Some(PathBuf::from("/inputs")),
+            cache_dir: Some(PathBuf::from("/cache")),
+            manifest_dir: Some(PathBuf::from("/crate")),
+        };
+        assert_eq!(
+            vec![
+                PathBuf::from("/inputs/day03.txt"),
+                PathBuf::from("/cache/day03.txt"),
+                PathBuf::from("/crate/src/input/input.txt"),
+                PathBuf::from("/crate/src/input/input.txt.enc"),
+            ],
+            resolver.candidates(3)
+        );
+
+        let resolver = Resolver { flag: Some(PathBuf::from("mine.txt")), ..resolver };
+        assert_eq!(vec![PathBuf::from("mine.txt")], resolver.candidates(3));
+
+        let resolver = Resolver { flag: Some(PathBuf::from("-")), ..resolver };
+        assert_eq!(PathBuf::from("-"), resolver.resolve(3).unwrap());
+    }
+
+    #[test]
+    fn test_resolve() {
+        let input_dir = std::env::temp_dir().join(format!("aoc-common-test-resolve-{}", std::process::id()));
+        std::fs::create_dir_all(&input_dir).unwrap();
+        std::fs::write(input_dir.join(file_name(24)), "").unwrap();
+        let resolver = Resolver {
+            input_dir: Some(input_dir.clone()),
+            cache_dir: Some(PathBuf::from("/nowhere")),
+            ..Resolver::default()
+        };
+        let resolved = resolver.resolve(24);
+        std::fs::remove_dir_all(&input_dir).unwrap();
+        assert_eq!(input_dir.join("day24.txt"), resolved.unwrap());
+    }
+
+    #[test]
+    fn test_resolve_lists_every_location() {
+        let resolver = Resolver {
+            input_dir: Some(PathBuf::from("/nowhere/inputs")),
+            manifest_dir: Some(PathBuf::from("/nowhere/crate")),
+            ..Resolver::default()
+        };
+        let error = resolver.resolve(7).unwrap_err();
+        assert!(matches!(&error, Error::InputNotFound { day: 7, tried } if tried.len() == 3));
+        let message = error.to_string();
+        assert!(message.contains("/nowhere/inputs/day07.txt"));
+        assert!(message.contains("/nowhere/crate/src/input/input.txt"));
+    }
+}
@@ -0,0 +1,163 @@
+use std::path::{Path, PathBuf};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use crate::{Error, Result};
+
+pub const EXTENSION: &str = "enc";
+pub const KEY_VAR: &str = "AOC_INPUT_KEY";
+
+// Sealed files start with this header, which is also authenticated with the contents.
+const MAGIC: &[u8] = b"aocseal1";
+
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+pub fn key_path() -> Option<PathBuf> {
+    super::resolve::config_dir().map(|dir| dir.join("input.key"))
+}
+
+pub fn is_sealed(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension == EXTENSION)
+}
+
+pub fn sealed_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".");
+    name.push(EXTENSION);
+    PathBuf::from(name)
+}
+
+// A 256-bit key, written as 64 hexadecimal digits in $AOC_INPUT_KEY or in the key file.
+#[derive(Clone, PartialEq)]
+pub struct Key([u8; KEY_LEN]);
+
+impl std::fmt::Debug for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Key(..)")
+    }
+}
+
+impl Key {
+    pub fn generate() -> Result<Self> {
+        let mut key = [0; KEY_LEN];
+        random_bytes(&mut key)?;
+        Ok(Self(key))
+    }
+
+    pub fn from_env() -> Result<Self> {
+        if let Ok(hex) = std::env::var(KEY_VAR) {
+            return Self::parse(&hex).ok_or_else(|| Error::Key(format!("${} isn't 64 hexadecimal digits", KEY_VAR)));
+        }
+        let path = key_path();
+        let hex = path.as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .ok_or_else(|| Error::Key(match &path {
+                Some(path) => format!("No input key: set ${} or write it to {}", KEY_VAR, path.display()),
+                None => format!("No input key: set ${}", KEY_VAR),
+            }))?;
+        Self::parse(&hex).ok_or_else(|| Error::Key(format!("{} isn't 64 hexadecimal digits", path.unwrap_or_default().display())))
+    }
+
+    pub fn parse(hex: &str) -> Option<Self> {
+        let hex = hex.trim();
+        if hex.len() != KEY_LEN * 2 || !hex.is_ascii() {
+            return None;
+        }
+        let mut key = [0; KEY_LEN];
+        for (byte, pair) in key.iter_mut().zip(hex.as_bytes().chunks(2)) {
+            *byte = u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok()?;
+        }
+        Some(Self(key))
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    fn cipher(&self) -> ChaCha20Poly1305 {
+        ChaCha20Poly1305::new(&self.0.into())
+    }
+
+    // The header, a random nonce, then the ChaCha20-Poly1305 ciphertext and its tag.
+    pub fn seal(&self, plaintext: &str) -> Result<Vec<u8>> {
+        let mut nonce = [0; NONCE_LEN];
+        random_bytes(&mut nonce)?;
+        let ciphertext = self.cipher()
+            .encrypt(Nonce::from_slice(&nonce), Payload { msg: plaintext.as_bytes(), aad: MAGIC })
+            .map_err(|_| Error::Key("The input is too large to seal".to_string()))?;
+        let mut sealed = MAGIC.to_vec();
+        sealed.extend_from_slice(&nonce);
+        sealed.extend(ciphertext);
+        Ok(sealed)
+    }
+
+    pub fn open(&self, sealed: &[u8]) -> Option<String> {
+        let rest = sealed.strip_prefix(MAGIC)?;
+        if rest.len() < NONCE_LEN {
+            return None;
+        }
+        let (nonce, rest) = rest.split_at(NONCE_LEN);
+        let plaintext = self.cipher()
+            .decrypt(Nonce::from_slice(nonce), Payload { msg: rest, aad: MAGIC })
+            .ok()?;
+        String::from_utf8(plaintext).ok()
+    }
+}
+
+// Decrypts a sealed input in memory, with the key from the environment.
+pub fn read_sealed(path: &Path) -> Result<String> {
+    let sealed = std::fs::read(path)
+        .map_err(|source| Error::Io { path: path.to_path_buf(), source })?;
+    Key::from_env()?
+        .open(&sealed)
+        .ok_or_else(|| Error::Key(format!("{} couldn't be decrypted: wrong key or corrupted file", path.display())))
+}
+
+// Keys and nonces only come from the operating system's random source, with no
+// weaker fallback when it's unavailable.
+fn random_bytes(buffer: &mut [u8]) -> Result<()> {
+    getrandom::getrandom(buffer)
+        .map_err(|error| Error::Key(format!("The system's random source is unavailable: {}", error)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_key_parse() {
+        let hex = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+        let key = Key::parse(&format!("{}\n", hex)).unwrap();
+        assert_eq!(hex, key.to_hex());
+        assert_eq!(None, Key::parse(&hex[2..]));
+        assert_eq!(None, Key::parse(&hex.replace('0', "g")));
+        assert_ne!(Key::generate().unwrap(), Key::generate().unwrap());
+    }
+
+    #[test]
+    fn test_seal_and_open() {
+        let key = Key::generate().unwrap();
+        let sealed = key.seal("1abc2\npqr3stu8vwx\n").unwrap();
+        assert!(sealed.starts_with(MAGIC));
+        assert_eq!(MAGIC.len() + NONCE_LEN + 18 + 16, sealed.len());
+        assert_ne!(sealed, key.seal("1abc2\npqr3stu8vwx\n").unwrap());
+        assert_eq!(Some("1abc2\npqr3stu8vwx\n".to_string()), key.open(&sealed));
+        assert_eq!(None, Key::generate().unwrap().open(&sealed));
+        assert_eq!(None, key.open(b"1abc2"));
+
+        let mut tampered = sealed.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert_eq!(None, key.open(&tampered));
+        let mut tampered = sealed;
+        tampered[0] ^= 1;
+        assert_eq!(None, key.open(&tampered));
+    }
+
+    #[test]
+    fn test_sealed_path() {
+        let path = sealed_path(Path::new("src/input/input.txt"));
+        assert_eq!(PathBuf::from("src/input/input.txt.enc"), path);
+        assert!(is_sealed(&path));
+        assert!(!is_sealed(Path::new("src/input/input.txt")));
+    }
+}
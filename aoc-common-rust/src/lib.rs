@@ -0,0 +1,18 @@
+pub mod answer;
+pub mod big;
+pub mod error;
+pub mod example;
+pub mod input;
+pub mod options;
+pub mod parse;
+pub mod solution;
+
+pub use answer::Answer;
+pub use big::BigUint;
+pub use error::{Error, ParseError, Result};
+pub use example::Example;
+pub use input::buffer::{Grid, Input};
+pub use input::{get_input_lines, read_input, read_input_from, split_grid, split_lines, stream_lines};
+pub use options::Options;
+pub use parse::{parse_lines, parse_numbers, parse_numbers_in};
+pub use solution::{Runner, Solution};
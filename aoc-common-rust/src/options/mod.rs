@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use crate::{Error, Result};
+
+// Settings for a variant of a day's puzzle, given to the runner as 'name=value,...'.
+// Days that have none reject any, so that a typo isn't silently ignored.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Options(BTreeMap<String, String>);
+
+impl Options {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.0.insert(name.to_string(), value.to_string());
+    }
+
+    pub fn expect_only(&self, known: &[&str]) -> Result<()> {
+        match self.0.keys().find(|name| !known.contains(&name.as_str())) {
+            Some(name) if known.is_empty() => Err(Error::Option {
+                name: name.clone(),
+                reason: "this day has no options".to_string(),
+            }),
+            Some(name) => Err(Error::Option {
+                name: name.clone(),
+                reason: format!("expected one of: {}", known.join(", ")),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for Options {
+    type Err = Error;
+
+    fn from_str(string: &str) -> Result<Self> {
+        let mut options = Self::default();
+        for pair in string.split(',').filter(|pair| !pair.trim().is_empty()) {
+            let Some((name, value)) = pair.split_once('=') else {
+                return Err(Error::Option { name: pair.trim().to_string(), reason: "expected 'name=value'".to_string() });
+            };
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(Error::Option { name: pair.trim().to_string(), reason: "the name is empty".to_string() });
+            }
+            if options.get(name).is_some() {
+                return Err(Error::Option { name: name.to_string(), reason: "it's set twice".to_string() });
+            }
+            options.set(name, value.trim());
+        }
+        Ok(options)
+    }
+}
+
+impl fmt::Display for Options {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pairs = self.0.iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>();
+        write!(f, "{}", pairs.join(","))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let options = "vocabulary = digits+french, rule=first-last".parse::<Options>().unwrap();
+        assert_eq!(Some("digits+french"), options.get("vocabulary"));
+        assert_eq!(Some("first-last"), options.get("rule"));
+        assert_eq!(None, options.get("other"));
+        assert_eq!("rule=first-last,vocabulary=digits+french", options.to_string());
+        assert!("".parse::<Options>().unwrap().is_empty());
+
+        assert!(matches!("rule".parse::<Options>(), Err(Error::Option { name, .. }) if name == "rule"));
+        assert!(matches!("=1".parse::<Options>(), Err(Error::Option { .. })));
+        assert!(matches!("a=1,a=2".parse::<Options>(), Err(Error::Option { name, .. }) if name == "a"));
+    }
+
+    #[test]
+    fn test_expect_only() {
+        let options = "vocabulary=roman".parse::<Options>().unwrap();
+        assert!(options.expect_only(&["vocabulary", "rule"]).is_ok());
+        assert_eq!(
+            "Bad option 'vocabulary': expected one of: rule",
+            options.expect_only(&["rule"]).unwrap_err().to_string()
+        );
+        assert_eq!(
+            "Bad option 'vocabulary': this day has no options",
+            options.expect_only(&[]).unwrap_err().to_string()
+        );
+        assert!(Options::default().expect_only(&[]).is_ok());
+    }
+}
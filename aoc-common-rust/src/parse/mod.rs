@@ -0,0 +1,64 @@
+use std::str::FromStr;
+use crate::{Error, Result};
+
+pub fn parse_numbers<T, C>(string: &str) -> Result<C>
+where
+    T: FromStr,
+    C: FromIterator<T>,
+{
+    parse_numbers_in(string, string)
+}
+
+// Same as `parse_numbers`, with errors positioned in `line`, which `string` is a slice of.
+pub fn parse_numbers_in<T, C>(line: &str, string: &str) -> Result<C>
+where
+    T: FromStr,
+    C: FromIterator<T>,
+{
+    string.split_whitespace()
+        .map(|part| part.parse::<T>().map_err(|_| Error::parse(line, part, "a number")))
+        .collect()
+}
+
+pub fn parse_lines<'a, T>(input: &'a str, mut parse: impl FnMut(&'a str) -> Result<T>) -> Result<Vec<T>> {
+    input.trim_end()
+        .lines()
+        .enumerate()
+        .map(|(index, line)| parse(line).map_err(|error| error.at_line(index + 1)))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use super::*;
+
+    #[test]
+    fn test_parse_numbers() {
+        assert_eq!(vec![41, 48, 83, 6], parse_numbers::<u32, Vec<_>>(" 41 48  83 6 ").unwrap());
+        assert_eq!(HashSet::from([1, 2]), parse_numbers::<u32, HashSet<_>>("1 2 1").unwrap());
+        assert!(parse_numbers::<u32, Vec<_>>("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_numbers_error() {
+        let error = parse_numbers::<u32, Vec<_>>("1 x2 3").unwrap_err();
+        assert!(matches!(error, Error::Parse(error) if error.column == 3 && error.text == "x2"));
+    }
+
+    #[test]
+    fn test_parse_numbers_in() {
+        const LINE: &str = "Card 1: 41 4x | 83";
+
+        let error = parse_numbers_in::<u32, Vec<_>>(LINE, &LINE[7..13]).unwrap_err();
+        assert!(matches!(error, Error::Parse(error) if error.column == 12 && error.text == "4x"));
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        assert_eq!(vec![vec![1, 2], vec![3]], parse_lines("1 2\n3\n\n", parse_numbers::<u32, Vec<_>>).unwrap());
+
+        let error = parse_lines("1 2\n3\n4 five", parse_numbers::<u32, Vec<_>>).unwrap_err();
+        assert!(matches!(error, Error::Parse(error) if (error.line, error.column) == (3, 3)));
+    }
+}
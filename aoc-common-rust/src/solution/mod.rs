@@ -0,0 +1,227 @@
+use std::any::Any;
+use std::io::BufRead;
+use crate::{read_input_from, Answer, Error, Input, Options, Result};
+use crate::input::resolve::Resolver;
+
+pub trait Solution {
+    const DAY: u8;
+
+    const MANIFEST_DIR: &'static str;
+
+    // The day crate's version, reported with its answers.
+    const VERSION: &'static str;
+
+    type Input;
+
+    fn parse(input: &Input) -> Result<Self::Input>;
+
+    // Days with variants override this to read them from the options, and keep them in
+    // the parsed input for the parts to use.
+    fn parse_with(input: &Input, options: &Options) -> Result<Self::Input> {
+        options.expect_only(&[])?;
+        Self::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer>;
+
+    fn part2(input: &Self::Input) -> Result<Answer>;
+
+    fn solve(input: &Self::Input, part: u8) -> Result<Answer> {
+        match part {
+            1 => Self::part1(input),
+            2 => Self::part2(input),
+            _ => Err(Error::Unsolved { day: Self::DAY, part }),
+        }
+    }
+
+    // Notes on a part's answer that don't make it wrong, such as lines it had to skip.
+    fn warnings(_input: &Self::Input, _part: u8) -> Vec<String> {
+        Vec::new()
+    }
+
+    // A trace of how the answers are found, for days that override it.
+    fn explain(_input: &Self::Input) -> Result<String> {
+        Err(Error::Unexplained { day: Self::DAY })
+    }
+
+    // Days that only need one line at a time override this to avoid buffering the input.
+    fn stream(reader: &mut dyn BufRead, parts: &[u8]) -> Result<Vec<Answer>> {
+        let input = Self::parse(&Input::from(read_input_from(reader)?))?;
+        parts.iter()
+            .map(|part| Self::solve(&input, *part))
+            .collect()
+    }
+
+    fn stream_with(reader: &mut dyn BufRead, parts: &[u8], options: &Options) -> Result<Vec<Answer>> {
+        if options.is_empty() {
+            return Self::stream(reader, parts);
+        }
+        let input = Self::parse_with(&Input::from(read_input_from(reader)?), options)?;
+        parts.iter()
+            .map(|part| Self::solve(&input, *part))
+            .collect()
+    }
+}
+
+// A parsed input, whose type is only known to the day that parsed it. It can be
+// shared between threads, so that the parts of a day can be solved concurrently.
+pub struct Parsed(Box<dyn Any + Send + Sync>);
+
+impl Parsed {
+    fn get<S: Solution>(&self) -> &S::Input
+    where
+        S::Input: 'static,
+    {
+        self.0.downcast_ref::<S::Input>()
+            .expect("The input should have been parsed by the same day")
+    }
+}
+
+pub trait Runner: Sync {
+    fn day(&self) -> u8;
+
+    fn manifest_dir(&self) -> &'static str;
+
+    fn version(&self) -> &'static str;
+
+    fn parse_with(&self, input: &Input, options: &Options) -> Result<Parsed>;
+
+    fn parse(&self, input: &Input) -> Result<Parsed> {
+        self.parse_with(input, &Options::default())
+    }
+
+    fn solve(&self, parsed: &Parsed, part: u8) -> Result<Answer>;
+
+    fn warnings(&self, parsed: &Parsed, part: u8) -> Vec<String>;
+
+    fn explain(&self, parsed: &Parsed) -> Result<String>;
+
+    fn run(&self, input: &Input, parts: &[u8]) -> Result<Vec<Answer>>;
+
+    fn stream_with(&self, reader: &mut dyn BufRead, parts: &[u8], options: &Options) -> Result<Vec<Answer>>;
+
+    fn stream(&self, reader: &mut dyn BufRead, parts: &[u8]) -> Result<Vec<Answer>> {
+        self.stream_with(reader, parts, &Options::default())
+    }
+}
+
+impl<S> Runner for S
+where
+    S: Solution + Sync,
+    S::Input: Send + Sync + 'static,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn manifest_dir(&self) -> &'static str {
+        S::MANIFEST_DIR
+    }
+
+    fn version(&self) -> &'static str {
+        S::VERSION
+    }
+
+    fn parse_with(&self, input: &Input, options: &Options) -> Result<Parsed> {
+        Ok(Parsed(Box::new(S::parse_with(input, options)?)))
+    }
+
+    fn solve(&self, parsed: &Parsed, part: u8) -> Result<Answer> {
+        S::solve(parsed.get::<S>(), part)
+    }
+
+    fn warnings(&self, parsed: &Parsed, part: u8) -> Vec<String> {
+        S::warnings(parsed.get::<S>(), part)
+    }
+
+    fn explain(&self, parsed: &Parsed) -> Result<String> {
+        S::explain(parsed.get::<S>())
+    }
+
+    fn run(&self, input: &Input, parts: &[u8]) -> Result<Vec<Answer>> {
+        let input = S::parse(input)?;
+        parts.iter()
+            .map(|part| S::solve(&input, *part))
+            .collect()
+    }
+
+    fn stream_with(&self, reader: &mut dyn BufRead, parts: &[u8], options: &Options) -> Result<Vec<Answer>> {
+        S::stream_with(reader, parts, options)
+    }
+}
+
+pub fn main(runner: &dyn Runner, parts: &[u8]) -> Result<()> {
+    let path = Resolver::from_env(None, runner.manifest_dir()).resolve(runner.day())?;
+    for result in runner.run(&Input::read(path)?, parts)? {
+        println!("{}", result);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+    use super::*;
+
+    thread_local! {
+        static PARSE_COUNT: Cell<usize> = const { Cell::new(0) };
+    }
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 42;
+
+        const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+        const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+
+        type Input = Vec<u32>;
+
+        fn parse(input: &Input) -> Result<Self::Input> {
+            PARSE_COUNT.set(PARSE_COUNT.get() + 1);
+            crate::parse_numbers(input)
+        }
+
+        fn part1(input: &Self::Input) -> Result<Answer> {
+            Ok(input.iter().sum::<u32>().into())
+        }
+
+        fn part2(_: &Self::Input) -> Result<Answer> {
+            Err(Error::Unsolved { day: Self::DAY, part: 2 })
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        assert_eq!(42, Sum.day());
+        assert_eq!(vec![Answer::Unsigned(6), Answer::Unsigned(6)], Sum.run(&Input::from("1 2 3"), &[1, 1]).unwrap());
+        assert_eq!(1, PARSE_COUNT.get());
+        assert!(matches!(Sum.run(&Input::from("1 2 3"), &[2]), Err(Error::Unsolved { day: 42, part: 2 })));
+        assert!(matches!(Sum.run(&Input::from("1 x 3"), &[1]), Err(Error::Parse(_))));
+        assert!(matches!(Sum.run(&Input::from("1 2 3"), &[3]), Err(Error::Unsolved { day: 42, part: 3 })));
+    }
+
+    #[test]
+    fn test_parse_then_solve() {
+        let parsed = Sum.parse(&Input::from("4 5")).unwrap();
+        assert_eq!(Answer::Unsigned(9), Sum.solve(&parsed, 1).unwrap());
+        assert_eq!(Answer::Unsigned(9), Sum.solve(&parsed, 1).unwrap());
+        assert!(matches!(Sum.solve(&parsed, 2), Err(Error::Unsolved { day: 42, part: 2 })));
+        assert!(matches!(Sum.explain(&parsed), Err(Error::Unexplained { day: 42 })));
+        assert!(Sum.warnings(&parsed, 1).is_empty());
+    }
+
+    #[test]
+    fn test_stream() {
+        assert_eq!(vec![Answer::Unsigned(6)], Sum.stream(&mut "1 2\n3\n".as_bytes(), &[1]).unwrap());
+    }
+
+    #[test]
+    fn test_options() {
+        let options = "scale=2".parse::<Options>().unwrap();
+        assert!(Sum.parse_with(&Input::from("1 2"), &Options::default()).is_ok());
+        assert!(matches!(Sum.parse_with(&Input::from("1 2"), &options), Err(Error::Option { name, .. }) if name == "scale"));
+        assert!(matches!(Sum.stream_with(&mut "1 2\n".as_bytes(), &[1], &options), Err(Error::Option { .. })));
+    }
+}
@@ -0,0 +1,66 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+// Counts the allocations made by each thread of the runner, so that the benchmarks can
+// report the ones their own thread makes, whatever other threads are doing meanwhile.
+pub(crate) struct Counting;
+
+// A const initialized Cell has nothing to allocate or drop, so it can be used from the
+// allocator. It's no longer there while the thread is torn down, and those allocations
+// aren't counted.
+fn count() {
+    let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count();
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count();
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count();
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+// The allocations made by the calling thread so far.
+pub(crate) fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_allocations() {
+        let before = allocations();
+        let boxed = std::hint::black_box(Box::new(42));
+        assert!(allocations() > before);
+        drop(boxed);
+    }
+
+    #[test]
+    fn test_other_threads_not_counted() {
+        let before = allocations();
+        std::thread::scope(|scope| {
+            scope.spawn(|| std::hint::black_box((0..1000).map(|_| Box::new(0u8)).collect::<Vec<_>>()));
+        });
+        // Spawning the thread allocates a little on this one, but not the boxes.
+        assert!(allocations() - before < 1000);
+    }
+}
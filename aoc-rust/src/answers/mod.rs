@@ -0,0 +1,169 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use aoc_common::{Answer, Error, Options};
+use crate::toml;
+
+pub(crate) const FILE_NAME: &str = "answers.toml";
+
+const PART_KEYS: [&str; 2] = ["part1", "part2"];
+
+pub(crate) fn default_path() -> PathBuf {
+    crate::workspace_dir().join(FILE_NAME)
+}
+
+// Accepted answers by day and part, stored as a small TOML file:
+//
+//     [day01]
+//     part1 = "53080"
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Answers {
+    days: BTreeSet<u8>,
+    entries: BTreeMap<(u8, u8), Answer>,
+}
+
+impl Answers {
+    pub(crate) fn load(path: &Path) -> aoc_common::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Self::parse(&aoc_common::read_input(path)?)
+    }
+
+    // The accepted answers are the puzzle's own: a run is only checked against them when it
+    // solves the default input, without any variant.
+    pub(crate) fn for_run(path: &Path, input: Option<&Path>, options: &Options) -> aoc_common::Result<Option<Self>> {
+        if input.is_some() || !options.is_empty() {
+            return Ok(None);
+        }
+        Self::load(path).map(Some)
+    }
+
+    pub(crate) fn save(&self, path: &Path) -> aoc_common::Result<()> {
+        std::fs::write(path, self.to_toml())
+            .map_err(|source| Error::Io {
+                path: path.to_path_buf(),
+                source,
+            })
+    }
+
+    pub(crate) fn parse(text: &str) -> aoc_common::Result<Self> {
+        let mut answers = Self::default();
+        for section in toml::parse(text)? {
+            let day = section.name.strip_prefix("day")
+                .and_then(|number| number.parse::<u8>().ok())
+                .ok_or_else(|| section.error("a '[dayNN]' header"))?;
+            answers.days.insert(day);
+            for entry in &section.entries {
+                let part = match entry.key.as_str() {
+                    "part1" => 1,
+                    "part2" => 2,
+                    _ => return Err(entry.error("'part1' or 'part2'")),
+                };
+                let Ok(answer) = entry.value.parse::<Answer>();
+                answers.entries.insert((day, part), answer);
+            }
+        }
+        Ok(answers)
+    }
+
+    pub(crate) fn to_toml(&self) -> String {
+        let mut toml = String::from("# Accepted answers, checked by 'aoc verify'.\n");
+        for day in self.days.iter().copied() {
+            let entries = self.entries.range((day, 0)..=(day, u8::MAX))
+                .map(|((_, part), answer)| (PART_KEYS[*part as usize - 1], toml::quoted(answer)))
+                .collect::<Vec<_>>();
+            toml::write_section(&mut toml, &format!("day{:02}", day), &entries);
+        }
+        toml
+    }
+
+    pub(crate) fn get(&self, day: u8, part: u8) -> Option<&Answer> {
+        self.entries.get(&(day, part))
+    }
+
+    pub(crate) fn set(&mut self, day: u8, part: u8, answer: Answer) {
+        self.days.insert(day);
+        self.entries.insert((day, part), answer);
+    }
+
+    // Adds an empty section for a day that has no accepted answer yet.
+    pub(crate) fn add_day(&mut self, day: u8) -> bool {
+        self.days.insert(day)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TOML: &str = "\
+# Accepted answers, checked by 'aoc verify'.
+
+[day01]
+part1 = \"142\"
+part2 = \"281\"
+
+[day03]
+part1 = \"4361\"
+";
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(TOML).unwrap();
+        assert_eq!(Some(&Answer::Unsigned(142)), answers.get(1, 1));
+        assert_eq!(Some(&Answer::Unsigned(281)), answers.get(1, 2));
+        assert_eq!(Some(&Answer::Unsigned(4361)), answers.get(3, 1));
+        assert_eq!(None, answers.get(3, 2));
+    }
+
+    #[test]
+    fn test_to_toml() {
+        let mut answers = Answers::default();
+        answers.set(3, 1, Answer::Unsigned(4361));
+        answers.set(1, 2, Answer::Unsigned(281));
+        answers.set(1, 1, Answer::Unsigned(142));
+        assert_eq!(TOML, answers.to_toml());
+        assert_eq!(answers, Answers::parse(&answers.to_toml()).unwrap());
+    }
+
+    #[test]
+    fn test_add_day() {
+        let mut answers = Answers::parse(TOML).unwrap();
+        assert!(!answers.add_day(3));
+        assert!(answers.add_day(5));
+        assert!(answers.to_toml().ends_with("[day03]\npart1 = \"4361\"\n\n[day05]\n"));
+        assert_eq!(answers, Answers::parse(&answers.to_toml()).unwrap());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let line = |text: &str| match Answers::parse(text) {
+            Err(Error::Parse(error)) => error.line,
+            _ => panic!("Expected a parse error for '{}'", text),
+        };
+        assert_eq!(1, line("part1 = \"1\""));
+        assert_eq!(2, line("[day01]\npart3 = \"1\""));
+        assert_eq!(3, line("[day01]\n\n[week1]"));
+        assert_eq!(2, line("[day01]\npart1"));
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        assert_eq!(Answers::default(), Answers::load(Path::new("/nowhere/answers.toml")).unwrap());
+    }
+
+    #[test]
+    fn test_for_run() {
+        let path = std::env::temp_dir().join(format!("aoc-answers-{}.toml", std::process::id()));
+        std::fs::write(&path, TOML).unwrap();
+        let default = Answers::for_run(&path, None, &Options::default());
+        let custom = Answers::for_run(&path, Some(Path::new("mine.txt")), &Options::default());
+        let stdin = Answers::for_run(&path, Some(Path::new("-")), &Options::default());
+        let variant = Answers::for_run(&path, None, &"rule=sum".parse().unwrap());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(Some(Answer::Unsigned(142)), default.unwrap().and_then(|answers| answers.get(1, 1).cloned()));
+        assert_eq!(None, custom.unwrap());
+        assert_eq!(None, stdin.unwrap());
+        assert_eq!(None, variant.unwrap());
+    }
+}
@@ -0,0 +1,253 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use aoc_common::{Error, Input, Runner};
+use crate::alloc::allocations;
+use crate::toml;
+
+pub(crate) const PHASES: [&str; 3] = ["parse", "part1", "part2"];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Options {
+    pub(crate) iterations: usize,
+    pub(crate) warmup: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            iterations: 100,
+            warmup: 10,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Measure {
+    pub(crate) median: Duration,
+    pub(crate) p95: Duration,
+    pub(crate) allocations: usize,
+}
+
+#[derive(Debug)]
+pub(crate) struct Phase {
+    pub(crate) day: u8,
+    pub(crate) name: &'static str,
+    pub(crate) measure: Measure,
+}
+
+fn measure(options: Options, mut phase: impl FnMut() -> aoc_common::Result<()>) -> aoc_common::Result<Measure> {
+    for _ in 0..options.warmup {
+        phase()?;
+    }
+    let iterations = options.iterations.max(1);
+    let mut durations = Vec::with_capacity(iterations);
+    let allocations_before = allocations();
+    for _ in 0..iterations {
+        let start = Instant::now();
+        phase()?;
+        durations.push(start.elapsed());
+    }
+    let allocations = (allocations() - allocations_before) / iterations;
+    let (median, p95) = percentiles(&mut durations);
+    Ok(Measure { median, p95, allocations })
+}
+
+fn percentiles(durations: &mut [Duration]) -> (Duration, Duration) {
+    durations.sort();
+    let p95_index = (durations.len() * 95).div_ceil(100).max(1) - 1;
+    (durations[durations.len() / 2], durations[p95_index])
+}
+
+pub(crate) fn bench(runner: &dyn Runner, input: &Input, options: Options) -> aoc_common::Result<Vec<Phase>> {
+    let day = runner.day();
+    let mut phases = vec![Phase {
+        day,
+        name: PHASES[0],
+        measure: measure(options, || runner.parse(input).map(drop))?,
+    }];
+    let parsed = runner.parse(input)?;
+    for part in 1..=2 {
+        match runner.solve(&parsed, part) {
+            Err(Error::Unsolved { .. }) => continue,
+            Err(error) => return Err(error),
+            Ok(_) => {}
+        }
+        phases.push(Phase {
+            day,
+            name: PHASES[part as usize],
+            measure: measure(options, || runner.solve(&parsed, part).map(drop))?,
+        });
+    }
+    Ok(phases)
+}
+
+// Measures saved by a previous 'aoc bench --save', by day and phase name.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Baseline {
+    measures: BTreeMap<(u8, String), Measure>,
+}
+
+impl Baseline {
+    pub(crate) fn from_phases(phases: &[Phase]) -> Self {
+        Self {
+            measures: phases.iter()
+                .map(|phase| ((phase.day, phase.name.to_string()), phase.measure))
+                .collect(),
+        }
+    }
+
+    pub(crate) fn load(path: &Path) -> aoc_common::Result<Self> {
+        Self::parse(&aoc_common::read_input(path)?)
+    }
+
+    pub(crate) fn save(&self, path: &Path) -> aoc_common::Result<()> {
+        std::fs::write(path, self.to_toml())
+            .map_err(|source| Error::Io {
+                path: path.to_path_buf(),
+                source,
+            })
+    }
+
+    pub(crate) fn parse(text: &str) -> aoc_common::Result<Self> {
+        let mut measures = BTreeMap::new();
+        for section in toml::parse(text)? {
+            let (day, phase) = section.name.split_once('.')
+                .and_then(|(day, phase)| Some((day.strip_prefix("day")?.parse::<u8>().ok()?, phase)))
+                .filter(|(_, phase)| PHASES.contains(phase))
+                .ok_or_else(|| section.error("a '[dayNN.phase]' header"))?;
+            let mut measure = Measure {
+                median: Duration::ZERO,
+                p95: Duration::ZERO,
+                allocations: 0,
+            };
+            for entry in &section.entries {
+                let value = entry.value.parse::<u64>()
+                    .map_err(|_| entry.error("a number"))?;
+                match entry.key.as_str() {
+                    "median_ns" => measure.median = Duration::from_nanos(value),
+                    "p95_ns" => measure.p95 = Duration::from_nanos(value),
+                    "allocations" => measure.allocations = value as usize,
+                    _ => return Err(entry.error("'median_ns', 'p95_ns' or 'allocations'")),
+                }
+            }
+            measures.insert((day, phase.to_string()), measure);
+        }
+        Ok(Self { measures })
+    }
+
+    pub(crate) fn to_toml(&self) -> String {
+        let mut toml = String::from("# Benchmark baseline, written by 'aoc bench --save'.\n");
+        for ((day, phase), measure) in &self.measures {
+            toml::write_section(&mut toml, &format!("day{:02}.{}", day, phase), &[
+                ("median_ns", measure.median.as_nanos().to_string()),
+                ("p95_ns", measure.p95.as_nanos().to_string()),
+                ("allocations", measure.allocations.to_string()),
+            ]);
+        }
+        toml
+    }
+
+    pub(crate) fn get(&self, day: u8, phase: &str) -> Option<&Measure> {
+        self.measures.get(&(day, phase.to_string()))
+    }
+}
+
+pub(crate) struct Report<'a> {
+    pub(crate) phases: &'a [Phase],
+    pub(crate) baseline: Option<&'a Baseline>,
+}
+
+impl fmt::Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:>3}  {:<5}  {:>12}  {:>12}  {:>8}", "Day", "Phase", "Median", "p95", "Allocs")?;
+        if self.baseline.is_some() {
+            write!(f, "  {:>9}", "Baseline")?;
+        }
+        for phase in self.phases {
+            let measure = phase.measure;
+            write!(
+                f,
+                "\n{:>3}  {:<5}  {:>12}  {:>12}  {:>8}",
+                phase.day,
+                phase.name,
+                format!("{:.1?}", measure.median),
+                format!("{:.1?}", measure.p95),
+                measure.allocations,
+            )?;
+            match self.baseline.map(|baseline| baseline.get(phase.day, phase.name)) {
+                Some(Some(before)) => write!(f, "  {:>9}", change(before.median, measure.median))?,
+                Some(None) => write!(f, "  {:>9}", "new")?,
+                None => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn change(before: Duration, after: Duration) -> String {
+    if before.is_zero() {
+        return "n/a".to_string();
+    }
+    let ratio = after.as_secs_f64() / before.as_secs_f64() - 1.0;
+    format!("{:+.1}%", ratio * 100.0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn phase(day: u8, name: &'static str, median: u64) -> Phase {
+        Phase {
+            day,
+            name,
+            measure: Measure {
+                median: Duration::from_nanos(median),
+                p95: Duration::from_nanos(median * 2),
+                allocations: 3,
+            },
+        }
+    }
+
+    #[test]
+    fn test_percentiles() {
+        let mut durations = (1..=100).rev().map(Duration::from_millis).collect::<Vec<_>>();
+        assert_eq!((Duration::from_millis(51), Duration::from_millis(95)), percentiles(&mut durations));
+
+        let mut durations = vec![Duration::from_millis(7)];
+        assert_eq!((Duration::from_millis(7), Duration::from_millis(7)), percentiles(&mut durations));
+    }
+
+    #[test]
+    fn test_bench() {
+        let input = Input::from("1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet");
+        let phases = bench(&aoc_01::Day01, &input, Options { iterations: 5, warmup: 1 }).unwrap();
+        assert_eq!(vec!["parse", "part1", "part2"], phases.iter().map(|phase| phase.name).collect::<Vec<_>>());
+        assert!(phases[0].measure.allocations > 0);
+
+        let phases = bench(&aoc_04::Day04, &Input::from("Card 1: 1 2 | 2 3"), Options { iterations: 5, warmup: 1 }).unwrap();
+        assert_eq!(vec!["parse", "part1"], phases.iter().map(|phase| phase.name).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_baseline() {
+        let baseline = Baseline::from_phases(&[phase(1, "parse", 1500), phase(1, "part2", 900)]);
+        let parsed = Baseline::parse(&baseline.to_toml()).unwrap();
+        assert_eq!(baseline, parsed);
+        assert_eq!(Duration::from_nanos(1500), parsed.get(1, "parse").unwrap().median);
+        assert!(Baseline::parse("[day01.solve]\nmedian_ns = 1").is_err());
+        assert!(Baseline::parse("[day01.parse]\nmedian_ns = fast").is_err());
+    }
+
+    #[test]
+    fn test_report() {
+        let phases = [phase(1, "parse", 1500), phase(1, "part1", 2000)];
+        let baseline = Baseline::from_phases(&[phase(1, "parse", 1000)]);
+        let report = Report { phases: &phases, baseline: Some(&baseline) }.to_string();
+        let lines = report.lines().collect::<Vec<_>>();
+        assert_eq!(3, lines.len());
+        assert!(lines[1].ends_with("+50.0%"));
+        assert!(lines[2].ends_with("new"));
+    }
+}
@@ -0,0 +1,560 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::str::FromStr;
+use aoc_common::Options;
+use crate::{bench, inputs, summary};
+use crate::error::{Error, Result};
+
+pub(crate) const USAGE: &str = "\
+Usage:
+    aoc run --day <DAY> [--part <PART>] [--input <PATH> | <PATH>] [--format <FORMAT>] [--mmap]
+            [--set <NAME=VALUE,...>]
+    aoc run --all [--format <FORMAT> | --json]
+    aoc run --day <DAY> [--part <PART>] (--profile <NAME> | --profiles) [--format <FORMAT>]
+    aoc watch --day <DAY>
+    aoc explain --day <DAY> [--input <PATH> | <PATH>] [--set <NAME=VALUE,...>]
+    aoc verify [--answers <PATH>] [--accept]
+    aoc fetch --day <DAY> [--server <URL>]
+    aoc submit --day <DAY> --part <PART> [--input <PATH> | <PATH>] [--server <URL>]
+    aoc examples --day <DAY> [--page <PATH>] [--server <URL>]
+    aoc inputs (seal | unseal) [--day <DAY>]
+    aoc new <DAY>
+    aoc bench [--day <DAY>] [--iterations <N>] [--warmup <N>] [--save <PATH>] [--baseline <PATH>]
+
+A path of '-' reads the input from stdin. Without a path, the input is looked
+up in $AOC_INPUT_DIR, then in the user cache directory, then in the day crate's
+src/input/input.txt, then in its encrypted src/input/input.txt.enc.
+
+'run' prints each answer with its parse and solve times and, for the day's
+default input, whether it matches the accepted one. '--all' solves every
+registered day and part in parallel. The format is 'text', a table, or 'json'
+or 'csv', records that also hold the input's SHA-256 and the day crate's
+version; '--json' is short for '--format json'. Streamed inputs have no
+timings. '--mmap' maps the input file into memory rather than reading it, for
+large generated inputs.
+
+'--set' selects a variant of the day's puzzle, with options the day defines; its
+answers aren't checked against the accepted ones. Day 1 has 'vocabulary', the
+tokens part 2's digits are written with: built-in sets (digits, zero, english,
+french, roman) and token files of 'token = value' lines, joined with '+'. It
+defaults to 'digits+english'. Day 1 also has 'rule', how a line's value is built
+from its digits: 'first-last[:K]', the first and last K digits (the default, with
+K = 1), 'all' of them, 'nth:N', the Nth digit, or 'sum'. Its 'missing' option
+sets what lines without those digits are worth in both parts: an 'error' (the
+default), nothing with 'skip', or N with 'default[:N]'. The lines skipped or
+defaulted are listed as warnings on stderr, except for streamed inputs.
+
+Profiles are other accounts' inputs, kept in the day crate's src/input/profiles
+as <name>.txt, or sealed as <name>.txt.enc, with their answers in <name>.expected
+('partP = answer' lines). '--profile' runs the day against one of them and
+'--profiles' against all of them, each checked against its own answers, and
+names the profiles that fail.
+
+'watch' runs the day and its tests, then rebuilds and runs them again whenever
+the day crate's src/ tree, manifest, build script or input changes. The view is
+redrawn in place, with how the timings changed since the last run.
+
+'explain' prints a trace of how the day finds its answers, for the days that have
+one. Day 1 lists each line's tokens with their byte spans, the digits the rule
+builds the line's value from and the line's value in both parts, and marks the
+lines where the parts differ with '*'. '--set' selects a variant as with 'run'.
+
+'verify' runs every registered day and compares the answers with the accepted
+ones, stored in answers.toml at the workspace root. '--accept' records the
+current answers as the accepted ones.
+
+'bench' times the parse, part 1 and part 2 phases of each day separately, and
+counts the allocations each iteration makes on the measuring thread. '--save'
+writes the measures as a baseline that a later '--baseline' compares against.
+
+'fetch' downloads the day's input into the user cache directory, where 'run'
+finds it, unless it's already there. It authenticates with the session cookie
+from $AOC_SESSION or from aoc/session in the user config directory. The server
+defaults to $AOC_SERVER, then https://adventofcode.com.
+
+'submit' runs one part and sends its answer to the server. Every submission is
+logged in submissions.toml in the user cache directory; answers already known
+to be wrong, and answers sent before the server's cooldown is over, are refused
+without contacting it.
+
+'examples' extracts the examples and their highlighted answers from the day's
+puzzle page, either saved at '--page' or downloaded from the server, and writes
+them to the day crate's src/input/examples as <name>.txt and <name>.expected.
+
+'inputs seal' encrypts each day's src/input/input.txt, and its profiles' inputs,
+into .txt.enc files, which can be committed; 'inputs unseal' decrypts them back.
+The key is read from $AOC_INPUT_KEY or from input.key in the user config
+directory, and sealing creates the key file when there is no key yet.
+
+'new' creates the aoc-<DAY>-rust crate and registers it in the workspace, the
+runner and answers.toml.";
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum Command {
+    Run {
+        day: u8,
+        part: Option<u8>,
+        input: Option<PathBuf>,
+        format: summary::Format,
+        map: bool,
+        options: Options,
+    },
+    RunAll {
+        format: summary::Format,
+    },
+    RunProfiles {
+        day: u8,
+        part: Option<u8>,
+        profile: Option<String>,
+        format: summary::Format,
+    },
+    Watch {
+        day: u8,
+    },
+    Explain {
+        day: u8,
+        input: Option<PathBuf>,
+        options: Options,
+    },
+    Verify {
+        answers: Option<PathBuf>,
+        accept: bool,
+    },
+    Fetch {
+        day: u8,
+        server: Option<String>,
+    },
+    Submit {
+        day: u8,
+        part: u8,
+        input: Option<PathBuf>,
+        server: Option<String>,
+    },
+    Examples {
+        day: u8,
+        page: Option<PathBuf>,
+        server: Option<String>,
+    },
+    Inputs {
+        action: inputs::Action,
+        day: Option<u8>,
+    },
+    New {
+        day: u8,
+    },
+    Bench {
+        day: Option<u8>,
+        options: bench::Options,
+        save: Option<PathBuf>,
+        baseline: Option<PathBuf>,
+    },
+}
+
+impl Command {
+    pub(crate) fn parse(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut args = args.into_iter();
+        match args.next().as_deref() {
+            Some("run") => {
+                let mut flags = Flags::parse(args, &["--all", "--json", "--profiles", "--mmap"])?;
+                if flags.switch("--all") {
+                    flags.expect_only(&["--all", "--json", "--format"], 0)?;
+                    return Ok(Command::RunAll { format: flags.format()? });
+                }
+                let profile = flags.value("--profile").map(str::to_string);
+                if profile.is_some() || flags.switch("--profiles") {
+                    flags.expect_only(&["--day", "--part", "--profile", "--profiles", "--format"], 0)?;
+                    if profile.is_some() && flags.switch("--profiles") {
+                        return Err(Error::Usage("Use either '--profile' or '--profiles'".to_string()));
+                    }
+                    return Ok(Command::RunProfiles {
+                        day: flags.required("--day")?,
+                        part: flags.part("--part")?,
+                        profile,
+                        format: flags.format()?,
+                    });
+                }
+                flags.expect_only(&["--day", "--part", "--input", "--format", "--mmap", "--set"], 1)?;
+                Ok(Command::Run {
+                    day: flags.required("--day")?,
+                    part: flags.part("--part")?,
+                    input: flags.input()?,
+                    format: flags.format()?,
+                    map: flags.switch("--mmap"),
+                    options: flags.options()?,
+                })
+            }
+            Some("watch") => {
+                let flags = Flags::parse(args, &[])?;
+                flags.expect_only(&["--day"], 0)?;
+                Ok(Command::Watch { day: flags.required("--day")? })
+            }
+            Some("explain") => {
+                let mut flags = Flags::parse(args, &[])?;
+                flags.expect_only(&["--day", "--input", "--set"], 1)?;
+                Ok(Command::Explain {
+                    day: flags.required("--day")?,
+                    input: flags.input()?,
+                    options: flags.options()?,
+                })
+            }
+            Some("verify") => {
+                let flags = Flags::parse(args, &["--accept"])?;
+                flags.expect_only(&["--answers", "--accept"], 0)?;
+                Ok(Command::Verify {
+                    answers: flags.path("--answers"),
+                    accept: flags.switch("--accept"),
+                })
+            }
+            Some("fetch") => {
+                let flags = Flags::parse(args, &[])?;
+                flags.expect_only(&["--day", "--server"], 0)?;
+                Ok(Command::Fetch {
+                    day: flags.required("--day")?,
+                    server: flags.value("--server").map(str::to_string),
+                })
+            }
+            Some("submit") => {
+                let mut flags = Flags::parse(args, &[])?;
+                flags.expect_only(&["--day", "--part", "--input", "--server"], 1)?;
+                Ok(Command::Submit {
+                    day: flags.required("--day")?,
+                    part: flags.part("--part")?
+                        .ok_or_else(|| Error::Usage("Missing '--part'".to_string()))?,
+                    input: flags.input()?,
+                    server: flags.value("--server").map(str::to_string),
+                })
+            }
+            Some("examples") => {
+                let flags = Flags::parse(args, &[])?;
+                flags.expect_only(&["--day", "--page", "--server"], 0)?;
+                Ok(Command::Examples {
+                    day: flags.required("--day")?,
+                    page: flags.path("--page"),
+                    server: flags.value("--server").map(str::to_string),
+                })
+            }
+            Some("inputs") => {
+                let action = match args.next().as_deref() {
+                    Some("seal") => inputs::Action::Seal,
+                    Some("unseal") => inputs::Action::Unseal,
+                    Some(action) => return Err(Error::Usage(format!("Unknown action '{}', expected seal or unseal", action))),
+                    None => return Err(Error::Usage("Missing action, expected seal or unseal".to_string())),
+                };
+                let flags = Flags::parse(args, &[])?;
+                flags.expect_only(&["--day"], 0)?;
+                Ok(Command::Inputs {
+                    action,
+                    day: flags.optional("--day")?,
+                })
+            }
+            Some("new") => {
+                let flags = Flags::parse(args, &[])?;
+                flags.expect_only(&[], 1)?;
+                let day = flags.positionals.first()
+                    .ok_or_else(|| Error::Usage("Missing day".to_string()))?;
+                Ok(Command::New {
+                    day: day.parse()
+                        .map_err(|_| Error::Usage(format!("Bad day '{}', expected a number", day)))?,
+                })
+            }
+            Some("bench") => {
+                let flags = Flags::parse(args, &[])?;
+                flags.expect_only(&["--day", "--iterations", "--warmup", "--save", "--baseline"], 0)?;
+                let defaults = bench::Options::default();
+                Ok(Command::Bench {
+                    day: flags.optional("--day")?,
+                    options: bench::Options {
+                        iterations: flags.optional("--iterations")?.unwrap_or(defaults.iterations),
+                        warmup: flags.optional("--warmup")?.unwrap_or(defaults.warmup),
+                    },
+                    save: flags.path("--save"),
+                    baseline: flags.path("--baseline"),
+                })
+            }
+            Some(command) => Err(Error::Usage(format!("Unknown command '{}'", command))),
+            None => Err(Error::Usage("Missing command".to_string())),
+        }
+    }
+}
+
+// Flags are either switches, which take no value, or '--name <value>' pairs.
+struct Flags {
+    values: HashMap<String, String>,
+    switches: HashSet<String>,
+    positionals: Vec<String>,
+}
+
+impl Flags {
+    fn parse(args: impl IntoIterator<Item = String>, switches: &[&str]) -> Result<Self> {
+        let mut flags = Flags {
+            values: HashMap::new(),
+            switches: HashSet::new(),
+            positionals: Vec::new(),
+        };
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if !arg.starts_with("--") {
+                flags.positionals.push(arg);
+            } else if switches.contains(&arg.as_str()) {
+                flags.switches.insert(arg);
+            } else {
+                let value = args.next()
+                    .ok_or_else(|| Error::Usage(format!("Missing value for '{}'", arg)))?;
+                if flags.values.insert(arg.clone(), value).is_some() {
+                    return Err(Error::Usage(format!("'{}' is set twice", arg)));
+                }
+            }
+        }
+        Ok(flags)
+    }
+
+    fn expect_only(&self, known: &[&str], max_positionals: usize) -> Result<()> {
+        if let Some(flag) = self.values.keys().chain(&self.switches).find(|flag| !known.contains(&flag.as_str())) {
+            return Err(Error::Usage(format!("Unknown flag '{}'", flag)));
+        }
+        if let Some(positional) = self.positionals.get(max_positionals) {
+            return Err(Error::Usage(format!("Unexpected argument '{}'", positional)));
+        }
+        Ok(())
+    }
+
+    fn switch(&self, flag: &str) -> bool {
+        self.switches.contains(flag)
+    }
+
+    fn value(&self, flag: &str) -> Option<&str> {
+        self.values.get(flag).map(String::as_str)
+    }
+
+    fn path(&self, flag: &str) -> Option<PathBuf> {
+        self.value(flag).map(PathBuf::from)
+    }
+
+    // The input is either '--input <PATH>' or a single positional path.
+    fn input(&mut self) -> Result<Option<PathBuf>> {
+        let input = self.path("--input");
+        let positional = self.positionals.pop().map(PathBuf::from);
+        if input.is_some() && positional.is_some() {
+            return Err(Error::Usage("The input is set twice".to_string()));
+        }
+        Ok(input.or(positional))
+    }
+
+    fn format(&self) -> Result<summary::Format> {
+        let format = self.value("--format")
+            .map(|value| value.parse::<summary::Format>()
+                .map_err(|_| Error::Usage(format!("Bad format '{}', expected text, json or csv", value))))
+            .transpose()?;
+        match (format, self.switch("--json")) {
+            (Some(_), true) => Err(Error::Usage("The format is set twice".to_string())),
+            (_, true) => Ok(summary::Format::Json),
+            (format, false) => Ok(format.unwrap_or_default()),
+        }
+    }
+
+    fn options(&self) -> Result<Options> {
+        self.value("--set")
+            .map(|value| value.parse::<Options>().map_err(|error| Error::Usage(error.to_string())))
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+
+    fn optional<T: FromStr>(&self, flag: &str) -> Result<Option<T>> {
+        self.value(flag)
+            .map(|value| value.parse::<T>()
+                .map_err(|_| Error::Usage(format!("Bad value '{}' for '{}', expected a number", value, flag))))
+            .transpose()
+    }
+
+    fn required<T: FromStr>(&self, flag: &str) -> Result<T> {
+        self.optional(flag)?
+            .ok_or_else(|| Error::Usage(format!("Missing '{}'", flag)))
+    }
+
+    fn part(&self, flag: &str) -> Result<Option<u8>> {
+        self.value(flag)
+            .map(|value| match value {
+                "1" => Ok(1),
+                "2" => Ok(2),
+                _ => Err(Error::Usage(format!("Bad part '{}', expected 1 or 2", value))),
+            })
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_parse_run() {
+        assert_eq!(
+            Command::Run { day: 3, part: Some(2), input: Some(PathBuf::from("path")), format: summary::Format::Text, map: false, options: Options::default() },
+            Command::parse(args("run --day 3 --part 2 --input path")).unwrap()
+        );
+        assert_eq!(
+            Command::Run { day: 1, part: None, input: Some(PathBuf::from("path")), format: summary::Format::Text, map: false, options: Options::default() },
+            Command::parse(args("run --input path --day 1")).unwrap()
+        );
+        assert_eq!(
+            Command::Run { day: 2, part: None, input: Some(PathBuf::from("-")), format: summary::Format::Csv, map: true, options: Options::default() },
+            Command::parse(args("run --day 2 --format csv --mmap -")).unwrap()
+        );
+        assert_eq!(
+            Command::Run { day: 1, part: None, input: None, format: summary::Format::Text, map: false, options: Options::default() },
+            Command::parse(args("run --day 1")).unwrap()
+        );
+        assert_eq!(
+            Command::Run { day: 1, part: None, input: None, format: summary::Format::Text, map: false, options: "vocabulary=roman".parse().unwrap() },
+            Command::parse(args("run --day 1 --set vocabulary=roman")).unwrap()
+        );
+        assert!(matches!(Command::parse(args("run --day 1 --set vocabulary")), Err(Error::Usage(_))));
+        assert!(matches!(Command::parse(args("run --all --set vocabulary=roman")), Err(Error::Usage(_))));
+    }
+
+    #[test]
+    fn test_parse_run_all() {
+        assert_eq!(Command::RunAll { format: summary::Format::Text }, Command::parse(args("run --all")).unwrap());
+        assert_eq!(Command::RunAll { format: summary::Format::Json }, Command::parse(args("run --json --all")).unwrap());
+        assert_eq!(Command::RunAll { format: summary::Format::Csv }, Command::parse(args("run --all --format csv")).unwrap());
+        assert!(matches!(Command::parse(args("run --all --day 1")), Err(Error::Usage(_))));
+        assert!(matches!(Command::parse(args("run --all --json --format csv")), Err(Error::Usage(_))));
+        assert!(matches!(Command::parse(args("run --all --format xml")), Err(Error::Usage(_))));
+        assert!(matches!(Command::parse(args("run --day 1 --json")), Err(Error::Usage(_))));
+    }
+
+    #[test]
+    fn test_parse_verify() {
+        assert_eq!(
+            Command::Verify { answers: None, accept: false },
+            Command::parse(args("verify")).unwrap()
+        );
+        assert_eq!(
+            Command::Verify { answers: Some(PathBuf::from("answers.toml")), accept: true },
+            Command::parse(args("verify --accept --answers answers.toml")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_fetch() {
+        assert_eq!(
+            Command::Fetch { day: 5, server: None },
+            Command::parse(args("fetch --day 5")).unwrap()
+        );
+        assert_eq!(
+            Command::Fetch { day: 5, server: Some("http://localhost:8080".to_string()) },
+            Command::parse(args("fetch --server http://localhost:8080 --day 5")).unwrap()
+        );
+        assert!(matches!(Command::parse(args("fetch")), Err(Error::Usage(_))));
+    }
+
+    #[test]
+    fn test_parse_submit() {
+        assert_eq!(
+            Command::Submit { day: 2, part: 1, input: None, server: None },
+            Command::parse(args("submit --day 2 --part 1")).unwrap()
+        );
+        assert_eq!(
+            Command::Submit { day: 2, part: 2, input: Some(PathBuf::from("-")), server: Some("http://localhost".to_string()) },
+            Command::parse(args("submit --day 2 --part 2 --server http://localhost -")).unwrap()
+        );
+        assert!(matches!(Command::parse(args("submit --day 2")), Err(Error::Usage(_))));
+    }
+
+    #[test]
+    fn test_parse_examples() {
+        assert_eq!(
+            Command::Examples { day: 1, page: Some(PathBuf::from("day01.html")), server: None },
+            Command::parse(args("examples --day 1 --page day01.html")).unwrap()
+        );
+        assert!(matches!(Command::parse(args("examples day01.html")), Err(Error::Usage(_))));
+    }
+
+    #[test]
+    fn test_parse_inputs() {
+        assert_eq!(
+            Command::Inputs { action: inputs::Action::Seal, day: None },
+            Command::parse(args("inputs seal")).unwrap()
+        );
+        assert_eq!(
+            Command::Inputs { action: inputs::Action::Unseal, day: Some(3) },
+            Command::parse(args("inputs unseal --day 3")).unwrap()
+        );
+        assert!(matches!(Command::parse(args("inputs")), Err(Error::Usage(_))));
+        assert!(matches!(Command::parse(args("inputs open")), Err(Error::Usage(_))));
+    }
+
+    #[test]
+    fn test_parse_new() {
+        assert_eq!(Command::New { day: 5 }, Command::parse(args("new 5")).unwrap());
+        assert!(matches!(Command::parse(args("new")), Err(Error::Usage(_))));
+        assert!(matches!(Command::parse(args("new five")), Err(Error::Usage(_))));
+        assert!(matches!(Command::parse(args("new 5 6")), Err(Error::Usage(_))));
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        assert!(matches!(
+            Command::parse(args("bench")).unwrap(),
+            Command::Bench { day: None, options: bench::Options { iterations: 100, warmup: 10 }, save: None, baseline: None }
+        ));
+        assert!(matches!(
+            Command::parse(args("bench --day 3 --iterations 20 --warmup 0 --baseline base.toml")).unwrap(),
+            Command::Bench { day: Some(3), options: bench::Options { iterations: 20, warmup: 0 }, save: None, baseline: Some(_) }
+        ));
+        assert!(matches!(Command::parse(args("bench --iterations many")), Err(Error::Usage(_))));
+    }
+
+    #[test]
+    fn test_parse_run_profiles() {
+        assert_eq!(
+            Command::RunProfiles { day: 1, part: Some(2), profile: Some("alice".to_string()), format: summary::Format::Text },
+            Command::parse(args("run --day 1 --part 2 --profile alice")).unwrap()
+        );
+        assert_eq!(
+            Command::RunProfiles { day: 1, part: None, profile: None, format: summary::Format::Csv },
+            Command::parse(args("run --day 1 --profiles --format csv")).unwrap()
+        );
+        assert!(matches!(Command::parse(args("run --day 1 --profiles --profile alice")), Err(Error::Usage(_))));
+        assert!(matches!(Command::parse(args("run --day 1 --profiles input.txt")), Err(Error::Usage(_))));
+        assert!(matches!(Command::parse(args("run --profiles")), Err(Error::Usage(_))));
+    }
+
+    #[test]
+    fn test_parse_watch() {
+        assert_eq!(Command::Watch { day: 4 }, Command::parse(args("watch --day 4")).unwrap());
+        assert!(matches!(Command::parse(args("watch")), Err(Error::Usage(_))));
+        assert!(matches!(Command::parse(args("watch --day 4 --part 1")), Err(Error::Usage(_))));
+    }
+
+    #[test]
+    fn test_parse_explain() {
+        assert_eq!(
+            Command::Explain { day: 1, input: Some(PathBuf::from("path")), options: "vocabulary=french".parse().unwrap() },
+            Command::parse(args("explain --day 1 --set vocabulary=french path")).unwrap()
+        );
+        assert_eq!(
+            Command::Explain { day: 1, input: None, options: Options::default() },
+            Command::parse(args("explain --day 1")).unwrap()
+        );
+        assert!(matches!(Command::parse(args("explain --day 1 --part 2")), Err(Error::Usage(_))));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(Command::parse(args("")), Err(Error::Usage(_))));
+        assert!(matches!(Command::parse(args("walk")), Err(Error::Usage(_))));
+        assert!(matches!(Command::parse(args("run --input path")), Err(Error::Usage(_))));
+        assert!(matches!(Command::parse(args("run --day one --input path")), Err(Error::Usage(_))));
+        assert!(matches!(Command::parse(args("run --day 1 --part 3 --input path")), Err(Error::Usage(_))));
+        assert!(matches!(Command::parse(args("run --day 1 --input")), Err(Error::Usage(_))));
+        assert!(matches!(Command::parse(args("run --day 1 --input a b")), Err(Error::Usage(_))));
+        assert!(matches!(Command::parse(args("run --day 1 --day 2")), Err(Error::Usage(_))));
+        assert!(matches!(Command::parse(args("run --day 1 --accept")), Err(Error::Usage(_))));
+        assert!(matches!(Command::parse(args("verify extra")), Err(Error::Usage(_))));
+    }
+}
@@ -0,0 +1,171 @@
+use std::path::{Path, PathBuf};
+use aoc_common::Answer;
+use aoc_common::input::resolve::{config_dir, file_name, YEAR};
+use crate::error::{Error, Result};
+use crate::http::{self, Request, Response};
+
+pub(crate) const DEFAULT_SERVER: &str = "https://adventofcode.com";
+pub(crate) const SERVER_VAR: &str = "AOC_SERVER";
+pub(crate) const SESSION_VAR: &str = "AOC_SESSION";
+
+const USER_AGENT: &str = concat!("aoc-rust/", env!("CARGO_PKG_VERSION"));
+
+pub(crate) fn session_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("session"))
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+// Talks to the puzzle server, authenticated with the user's session cookie.
+#[derive(Debug)]
+pub(crate) struct Client {
+    server: String,
+    session: String,
+}
+
+impl Client {
+    // A line break in the session would end the Cookie header and start another one.
+    pub(crate) fn new(server: &str, session: &str) -> Result<Self> {
+        let session = session.trim();
+        if session.contains(['\r', '\n']) {
+            return Err(Error::Http("The session cookie can't contain a line break".to_string()));
+        }
+        Ok(Self {
+            server: server.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+        })
+    }
+
+    // The server defaults to the real site; the session comes from $AOC_SESSION or the session file.
+    pub(crate) fn from_env(server: Option<String>) -> Result<Self> {
+        let server = server
+            .or_else(|| std::env::var(SERVER_VAR).ok())
+            .unwrap_or_else(|| DEFAULT_SERVER.to_string());
+        let session = match std::env::var(SESSION_VAR) {
+            Ok(session) => session,
+            Err(_) => {
+                let path = session_path();
+                path.as_ref()
+                    .and_then(|path| std::fs::read_to_string(path).ok())
+                    .ok_or(Error::MissingSession(path))?
+            }
+        };
+        Self::new(&server, &session)
+    }
+
+    pub(crate) fn day_url(&self, day: u8, page: &str) -> String {
+        format!("{}/{}/day/{}{}", self.server, YEAR, day, page)
+    }
+
+    pub(crate) fn send(&self, method: &str, url: &str, body: Option<String>) -> Result<Response> {
+        let mut headers = vec![
+            ("Cookie", format!("session={}", self.session)),
+            ("User-Agent", USER_AGENT.to_string()),
+        ];
+        if body.is_some() {
+            headers.push(("Content-Type", "application/x-www-form-urlencoded".to_string()));
+        }
+        http::send(&Request { method, url, headers, body })
+    }
+
+    pub(crate) fn submit(&self, day: u8, part: u8, answer: &Answer) -> Result<Response> {
+        let body = format!("level={}&answer={}", part, form_encode(&answer.to_string()));
+        self.send("POST", &self.day_url(day, "/answer"), Some(body))
+    }
+
+    // Inputs never change, so a cached one is never downloaded again.
+    pub(crate) fn fetch(&self, day: u8, cache_dir: &Path) -> Result<Fetched> {
+        let path = cache_dir.join(file_name(day));
+        if path.is_file() {
+            return Ok(Fetched::Cached(path));
+        }
+        let url = self.day_url(day, "/input");
+        let response = self.send("GET", &url, None)?;
+        if !response.is_success() {
+            return Err(Error::Http(format!(
+                "GET {} failed with status {}: {}", url, response.status, response.body.trim()
+            )));
+        }
+        let io_error = |path: &Path| {
+            let path = path.to_path_buf();
+            move |source| Error::Common(aoc_common::Error::Io { path, source })
+        };
+        std::fs::create_dir_all(cache_dir).map_err(io_error(cache_dir))?;
+        // Written aside then renamed, so an interrupted download never looks cached.
+        let partial = path.with_extension("part");
+        std::fs::write(&partial, &response.body).map_err(io_error(&partial))?;
+        std::fs::rename(&partial, &path).map_err(io_error(&path))?;
+        Ok(Fetched::Downloaded(path))
+    }
+}
+
+fn form_encode(value: &str) -> String {
+    value.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+            b' ' => "+".to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::http::test::{ok, serve};
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-client-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_fetch() {
+        let (server, requests, handle) = serve(vec![ok("1abc2\npqr3stu8vwx\n")]);
+        let client = Client::new(&format!("{}/", server), "secret\n").unwrap();
+        let dir = temp_dir("fetch");
+
+        let path = dir.join("day07.txt");
+        assert_eq!(Fetched::Downloaded(path.clone()), client.fetch(7, &dir).unwrap());
+        // The stand-in only answers once: a second download would fail to connect.
+        handle.join().unwrap();
+        assert_eq!(Fetched::Cached(path.clone()), client.fetch(7, &dir).unwrap());
+
+        assert_eq!("1abc2\npqr3stu8vwx\n", std::fs::read_to_string(&path).unwrap());
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2023/day/7/input HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=secret\r\n"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(Client::new("http://localhost", "secret\r\n").is_ok());
+        for session in ["secret\r\nX-Injected: 1", "secret\nX-Injected: 1", "secret\rX"] {
+            assert!(matches!(Client::new("http://localhost", session), Err(Error::Http(_))));
+        }
+    }
+
+    #[test]
+    fn test_form_encode() {
+        assert_eq!("-12345", form_encode("-12345"));
+        assert_eq!("a+b%26c%3D", form_encode("a b&c="));
+    }
+
+    #[test]
+    fn test_fetch_failure() {
+        let body = "Puzzle inputs differ by user.  Please log in to get your puzzle input.";
+        let response = format!("HTTP/1.1 400 Bad Request\r\nContent-Length: {}\r\n\r\n{}", body.len(), body);
+        let (server, _requests, handle) = serve(vec![response]);
+        let dir = temp_dir("failure");
+
+        assert!(matches!(Client::new(&server, "expired").unwrap().fetch(7, &dir), Err(Error::Http(_))));
+        handle.join().unwrap();
+        assert!(!dir.join("day07.txt").exists());
+    }
+}
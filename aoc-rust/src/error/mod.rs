@@ -0,0 +1,49 @@
+use std::fmt;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub(crate) enum Error {
+    Usage(String),
+    UnknownDay(u8),
+    Regression(usize),
+    Failed(usize),
+    Profiles(Vec<String>),
+    Scaffold(String),
+    Http(String),
+    Refused(String),
+    MissingSession(Option<PathBuf>),
+    Common(aoc_common::Error),
+}
+
+pub(crate) type Result<T> = std::result::Result<T, Error>;
+
+impl From<aoc_common::Error> for Error {
+    fn from(error: aoc_common::Error) -> Self {
+        Error::Common(error)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Usage(message) => write!(f, "{}\n\n{}", message, crate::cli::USAGE),
+            Error::UnknownDay(day) => write!(f, "Day {} isn't registered", day),
+            Error::Regression(count) => write!(f, "{} answer(s) regressed", count),
+            Error::Failed(count) => write!(f, "{} part(s) failed", count),
+            Error::Profiles(names) => write!(f, "{} profile(s) failed: {}", names.len(), names.join(", ")),
+            Error::Scaffold(message) => write!(f, "{}", message),
+            Error::Http(message) => write!(f, "{}", message),
+            Error::Refused(message) => write!(f, "{}", message),
+            Error::MissingSession(path) => {
+                write!(f, "No session cookie: set ${}", crate::client::SESSION_VAR)?;
+                match path {
+                    Some(path) => write!(f, " or write it to {}", path.display()),
+                    None => Ok(()),
+                }
+            }
+            Error::Common(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,250 @@
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::process::{Command, Stdio};
+use crate::error::{Error, Result};
+
+#[derive(Debug)]
+pub(crate) struct Request<'a> {
+    pub(crate) method: &'a str,
+    pub(crate) url: &'a str,
+    pub(crate) headers: Vec<(&'a str, String)>,
+    pub(crate) body: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct Response {
+    pub(crate) status: u16,
+    pub(crate) body: String,
+}
+
+impl Response {
+    pub(crate) fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Url<'a> {
+    host: &'a str,
+    port: u16,
+    path: &'a str,
+}
+
+fn parse_url(url: &str) -> Result<Url<'_>> {
+    let rest = url.strip_prefix("http://")
+        .ok_or_else(|| Error::Http(format!("Unsupported URL '{}'", url)))?;
+    let (authority, path) = match rest.find('/') {
+        Some(index) => (&rest[..index], &rest[index..]),
+        None => (rest, "/"),
+    };
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => (host, port.parse::<u16>()
+            .map_err(|_| Error::Http(format!("Bad port in URL '{}'", url)))?),
+        None => (authority, 80),
+    };
+    Ok(Url { host, port, path })
+}
+
+// Plain HTTP is enough for the local stand-ins used in tests; HTTPS goes through curl.
+pub(crate) fn send(request: &Request) -> Result<Response> {
+    if request.url.starts_with("https://") {
+        send_with_curl(request)
+    } else {
+        send_over_tcp(request)
+    }
+}
+
+fn send_over_tcp(request: &Request) -> Result<Response> {
+    let url = parse_url(request.url)?;
+    let io_error = |error: std::io::Error| Error::Http(format!("{} {}: {}", request.method, request.url, error));
+    let mut stream = TcpStream::connect((url.host, url.port)).map_err(io_error)?;
+    let mut message = format!(
+        "{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n",
+        request.method, url.path, url.host
+    );
+    for (name, value) in &request.headers {
+        message.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    let body = request.body.as_deref().unwrap_or("");
+    message.push_str(&format!("Content-Length: {}\r\n\r\n{}", body.len(), body));
+    stream.write_all(message.as_bytes()).map_err(io_error)?;
+    let mut raw = Vec::new();
+    stream.read_to_end(&mut raw).map_err(io_error)?;
+    parse_response(&String::from_utf8_lossy(&raw))
+}
+
+fn parse_response(raw: &str) -> Result<Response> {
+    let bad_response = || Error::Http("Bad HTTP response".to_string());
+    let (head, body) = raw.split_once("\r\n\r\n").ok_or_else(bad_response)?;
+    let mut lines = head.lines();
+    let status = lines.next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|status| status.parse::<u16>().ok())
+        .ok_or_else(bad_response)?;
+    let chunked = lines
+        .filter_map(|line| line.split_once(':'))
+        .any(|(name, value)| name.eq_ignore_ascii_case("transfer-encoding") && value.trim().eq_ignore_ascii_case("chunked"));
+    let body = if chunked {
+        decode_chunked(body).ok_or_else(bad_response)?
+    } else {
+        body.to_string()
+    };
+    Ok(Response { status, body })
+}
+
+fn decode_chunked(mut body: &str) -> Option<String> {
+    let mut decoded = String::new();
+    loop {
+        let (size, rest) = body.split_once("\r\n")?;
+        let size = usize::from_str_radix(size.trim(), 16).ok()?;
+        if size == 0 {
+            return Some(decoded);
+        }
+        decoded.push_str(rest.get(..size)?);
+        body = rest.get(size..)?.strip_prefix("\r\n")?;
+    }
+}
+
+// The headers go through curl's stdin rather than its arguments, which any other
+// user can read while it runs, since they carry the session cookie.
+fn send_with_curl(request: &Request) -> Result<Response> {
+    let curl_error = |error: std::io::Error| Error::Http(format!("Couldn't run curl: {}", error));
+    let mut command = Command::new("curl");
+    command.args(["--silent", "--show-error", "--request", request.method, "--write-out", "\n%{http_code}"]);
+    command.args(["--header", "@-"]);
+    if let Some(body) = &request.body {
+        command.arg("--data").arg(body);
+    }
+    let mut child = command.arg(request.url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(curl_error)?;
+    let headers = request.headers
+        .iter()
+        .map(|(name, value)| format!("{}: {}\n", name, value))
+        .collect::<String>();
+    // Dropping stdin once it's written closes it, so curl stops reading headers.
+    child.stdin
+        .take()
+        .expect("curl's stdin is piped")
+        .write_all(headers.as_bytes())
+        .map_err(curl_error)?;
+    let output = child.wait_with_output().map_err(curl_error)?;
+    if !output.status.success() {
+        return Err(Error::Http(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+    let output = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = output.rsplit_once('\n')
+        .ok_or_else(|| Error::Http("Bad curl output".to_string()))?;
+    Ok(Response {
+        status: status.trim().parse().map_err(|_| Error::Http("Bad curl output".to_string()))?,
+        body: body.to_string(),
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::io::BufRead;
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread::JoinHandle;
+    use super::*;
+
+    // A local stand-in that answers each connection with the next canned response,
+    // and reports the requests it received.
+    pub(crate) fn serve(responses: Vec<String>) -> (String, mpsc::Receiver<String>, JoinHandle<()>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+        let handle = std::thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = std::io::BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(length) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        content_length = length.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                request.push_str(&String::from_utf8(body).unwrap());
+                sender.send(request).unwrap();
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        (url, receiver, handle)
+    }
+
+    pub(crate) fn ok(body: &str) -> String {
+        format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}", body.len(), body)
+    }
+
+    #[test]
+    fn test_parse_url() {
+        assert_eq!(Url { host: "127.0.0.1", port: 8080, path: "/2023/day/1/input" }, parse_url("http://127.0.0.1:8080/2023/day/1/input").unwrap());
+        assert_eq!(Url { host: "localhost", port: 80, path: "/" }, parse_url("http://localhost").unwrap());
+        assert!(parse_url("ftp://localhost").is_err());
+        assert!(parse_url("http://localhost:http/").is_err());
+    }
+
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(Response { status: 404, body: "Not found".to_string() }, parse_response("HTTP/1.1 404 Not Found\r\nServer: x\r\n\r\nNot found").unwrap());
+        assert_eq!(
+            Response { status: 200, body: "Wikipedia".to_string() },
+            parse_response("HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\nWiki\r\n5\r\npedia\r\n0\r\n\r\n").unwrap()
+        );
+        assert!(parse_response("garbage").is_err());
+    }
+
+    #[test]
+    fn test_send() {
+        let (url, requests, handle) = serve(vec![ok("1abc2\n")]);
+        let response = send(&Request {
+            method: "POST",
+            url: &format!("{}/path", url),
+            headers: vec![("Cookie", "session=abc".to_string())],
+            body: Some("level=1".to_string()),
+        }).unwrap();
+        handle.join().unwrap();
+
+        assert_eq!(Response { status: 200, body: "1abc2\n".to_string() }, response);
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /path HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=abc\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=1"));
+    }
+
+    #[test]
+    fn test_send_with_curl() {
+        // The stand-in speaks plain HTTP, which curl does as well; without curl there's nothing to test.
+        if Command::new("curl").arg("--version").output().is_err() {
+            return;
+        }
+        let (url, requests, handle) = serve(vec![ok("1abc2\n")]);
+        let response = send_with_curl(&Request {
+            method: "POST",
+            url: &format!("{}/path", url),
+            headers: vec![("Cookie", "session=abc".to_string()), ("User-Agent", "test".to_string())],
+            body: Some("level=1".to_string()),
+        }).unwrap();
+        handle.join().unwrap();
+
+        assert_eq!(Response { status: 200, body: "1abc2\n".to_string() }, response);
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /path HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=abc\r\n"));
+        assert!(request.contains("User-Agent: test\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=1"));
+    }
+}
@@ -0,0 +1,157 @@
+use std::path::{Path, PathBuf};
+use aoc_common::input::seal::{self, Key};
+use crate::error::{Error, Result};
+use crate::profiles;
+
+const INPUT_FILE: &str = "input.txt";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Action {
+    Seal,
+    Unseal,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum Outcome {
+    Written(PathBuf),
+    Unchanged(PathBuf),
+    Missing(PathBuf),
+}
+
+pub(crate) fn input_dir(manifest_dir: &str) -> PathBuf {
+    Path::new(manifest_dir).join("src").join("input")
+}
+
+// The day's own input and every profile's, by the path of their plain text.
+pub(crate) fn plain_paths(manifest_dir: &str) -> Result<Vec<PathBuf>> {
+    let dir = profiles::dir(manifest_dir);
+    Ok(std::iter::once(input_dir(manifest_dir).join(INPUT_FILE))
+        .chain(profiles::names(&dir)?.iter().map(|name| profiles::plain_path(&dir, name)))
+        .collect())
+}
+
+fn io_error(path: &Path) -> impl FnOnce(std::io::Error) -> Error + '_ {
+    move |source| Error::Common(aoc_common::Error::Io { path: path.to_path_buf(), source })
+}
+
+// Sealing picks a new nonce every time, so an input that's already sealed is left alone
+// rather than rewritten with different bytes.
+pub(crate) fn apply(action: Action, plain: &Path, key: &Key) -> Result<Outcome> {
+    let plain = plain.to_path_buf();
+    let sealed = seal::sealed_path(&plain);
+    let current_plain = std::fs::read_to_string(&plain).ok();
+    let current_sealed = std::fs::read(&sealed).ok();
+    let opened = current_sealed.as_ref()
+        .map(|bytes| key.open(bytes).ok_or_else(|| Error::Common(aoc_common::Error::Key(format!(
+            "{} couldn't be decrypted: wrong key or corrupted file", sealed.display()
+        )))))
+        .transpose();
+    match action {
+        Action::Seal => {
+            let Some(input) = current_plain else {
+                return Ok(Outcome::Missing(plain));
+            };
+            if opened?.as_ref() == Some(&input) {
+                return Ok(Outcome::Unchanged(sealed));
+            }
+            std::fs::write(&sealed, key.seal(&input)?).map_err(io_error(&sealed))?;
+            Ok(Outcome::Written(sealed))
+        }
+        Action::Unseal => {
+            let Some(input) = opened? else {
+                return Ok(Outcome::Missing(sealed));
+            };
+            if current_plain.as_ref() == Some(&input) {
+                return Ok(Outcome::Unchanged(plain));
+            }
+            std::fs::write(&plain, input).map_err(io_error(&plain))?;
+            Ok(Outcome::Written(plain))
+        }
+    }
+}
+
+// Sealing without any key creates one, which then has to be shared with the team.
+pub(crate) fn key(action: Action) -> Result<(Key, Option<PathBuf>)> {
+    let path = seal::key_path();
+    let missing = std::env::var_os(seal::KEY_VAR).is_none() && path.as_ref().is_some_and(|path| !path.exists());
+    if action == Action::Unseal || !missing {
+        return Ok((Key::from_env()?, None));
+    }
+    let path = path.unwrap_or_default();
+    let key = Key::generate()?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(io_error(dir))?;
+    }
+    write_private(&path, &key.to_hex()).map_err(io_error(&path))?;
+    Ok((key, Some(path)))
+}
+
+#[cfg(unix)]
+fn write_private(path: &Path, contents: &str) -> std::io::Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)?
+        .write_all(contents.as_bytes())
+}
+
+#[cfg(not(unix))]
+fn write_private(path: &Path, contents: &str) -> std::io::Result<()> {
+    std::fs::write(path, contents)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-inputs-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_seal_and_unseal() {
+        let dir = temp_dir("roundtrip");
+        let key = Key::generate().unwrap();
+        let plain = dir.join("input.txt");
+        let sealed = dir.join("input.txt.enc");
+
+        assert_eq!(Outcome::Missing(plain.clone()), apply(Action::Seal, &plain, &key).unwrap());
+        assert_eq!(Outcome::Missing(sealed.clone()), apply(Action::Unseal, &plain, &key).unwrap());
+
+        std::fs::write(&plain, "1abc2\n").unwrap();
+        assert_eq!(Outcome::Written(sealed.clone()), apply(Action::Seal, &plain, &key).unwrap());
+        let bytes = std::fs::read(&sealed).unwrap();
+        assert_eq!(Outcome::Unchanged(sealed.clone()), apply(Action::Seal, &plain, &key).unwrap());
+        assert_eq!(bytes, std::fs::read(&sealed).unwrap());
+        assert_eq!(Some("1abc2\n".to_string()), key.open(&bytes));
+
+        std::fs::remove_file(&plain).unwrap();
+        assert_eq!(Outcome::Written(plain.clone()), apply(Action::Unseal, &plain, &key).unwrap());
+        assert_eq!("1abc2\n", std::fs::read_to_string(&plain).unwrap());
+        assert_eq!(Outcome::Unchanged(plain.clone()), apply(Action::Unseal, &plain, &key).unwrap());
+
+        assert!(apply(Action::Unseal, &plain, &Key::generate().unwrap()).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_plain_paths() {
+        let manifest_dir = temp_dir("profiles");
+        let manifest = manifest_dir.to_str().unwrap();
+        let profiles = profiles::dir(manifest);
+        std::fs::create_dir_all(&profiles).unwrap();
+        std::fs::write(profiles.join("bob.txt.enc"), "").unwrap();
+        std::fs::write(profiles.join("alice.txt"), "").unwrap();
+        assert_eq!(
+            vec![input_dir(manifest).join("input.txt"), profiles.join("alice.txt"), profiles.join("bob.txt")],
+            plain_paths(manifest).unwrap()
+        );
+        std::fs::remove_dir_all(&manifest_dir).unwrap();
+    }
+}
@@ -0,0 +1,47 @@
+// Just enough JSON writing for the runner's machine-readable outputs.
+pub(crate) fn string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+pub(crate) fn optional(value: Option<&str>) -> String {
+    value.map_or_else(|| "null".to_string(), string)
+}
+
+// Writes an object from already encoded values, keeping the fields' order.
+pub(crate) fn object(fields: &[(&str, String)]) -> String {
+    let fields = fields.iter()
+        .map(|(name, value)| format!("{}: {}", string(name), value))
+        .collect::<Vec<_>>();
+    format!("{{{}}}", fields.join(", "))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_string() {
+        assert_eq!("\"53080\"", string("53080"));
+        assert_eq!("\"a \\\"b\\\"\\n\\\\c\\u0001\"", string("a \"b\"\n\\c\u{1}"));
+        assert_eq!("null", optional(None));
+    }
+
+    #[test]
+    fn test_object() {
+        assert_eq!("{\"day\": 1, \"answer\": \"142\"}", object(&[("day", 1.to_string()), ("answer", string("142"))]));
+    }
+}
@@ -0,0 +1,242 @@
+mod alloc;
+mod answers;
+mod bench;
+mod cli;
+mod client;
+mod error;
+mod http;
+mod inputs;
+mod json;
+mod pool;
+mod page;
+mod profiles;
+mod registry;
+mod scaffold;
+mod submit;
+mod summary;
+mod toml;
+mod verify;
+mod watch;
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use aoc_common::{read_input, Input};
+use aoc_common::input::is_stdin;
+use aoc_common::input::resolve::Resolver;
+use cli::Command;
+use error::{Error, Result};
+
+#[global_allocator]
+static ALLOCATOR: alloc::Counting = alloc::Counting;
+
+fn main() -> ExitCode {
+    match run(std::env::args().skip(1)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn workspace_dir() -> PathBuf {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    manifest_dir.parent().unwrap_or(manifest_dir).to_path_buf()
+}
+
+fn run(args: impl IntoIterator<Item = String>) -> Result<()> {
+    match Command::parse(args)? {
+        Command::Run { day, part, input, format, map, options } => {
+            let day = registry::find(day)?;
+            let path = Resolver::from_env(input.as_deref(), day.manifest_dir()).resolve(day.day())?;
+            let parts = match part {
+                Some(part) => vec![part],
+                None => vec![1, 2],
+            };
+            let answers = answers::Answers::for_run(&answers::default_path(), input.as_deref(), &options)?;
+            let rows = if is_stdin(&path) {
+                summary::stream_day(day, &mut std::io::stdin().lock(), &options, &parts, answers.as_ref())?
+            } else {
+                let input = if map { Input::map(path)? } else { Input::read(path)? };
+                summary::run_day(day, &input, &options, &parts, answers.as_ref())
+            };
+            println!("{}", summary::write(format, &rows));
+            for warning in summary::warnings(&rows) {
+                eprintln!("Warning: {}", warning);
+            }
+            let failures = rows.iter().filter(|row| matches!(row.check.status, verify::Status::Failed(_))).count();
+            if failures > 0 {
+                return Err(Error::Failed(failures));
+            }
+            Ok(())
+        }
+        Command::RunAll { format } => {
+            let answers = answers::Answers::load(&answers::default_path())?;
+            let rows = summary::run_all(registry::DAYS, &answers, |runner| Resolver::from_env(None, runner.manifest_dir()));
+            println!("{}", summary::write(format, &rows));
+            for warning in summary::warnings(&rows) {
+                eprintln!("Warning: {}", warning);
+            }
+            let regressions = rows.iter().filter(|row| row.check.is_regression()).count();
+            if regressions > 0 {
+                return Err(Error::Regression(regressions));
+            }
+            Ok(())
+        }
+        Command::Explain { day, input, options } => {
+            let runner = registry::find(day)?;
+            let path = Resolver::from_env(input.as_deref(), runner.manifest_dir()).resolve(day)?;
+            let parsed = runner.parse_with(&Input::read(path)?, &options)?;
+            println!("{}", runner.explain(&parsed)?);
+            Ok(())
+        }
+        Command::Watch { day } => Ok(watch::watch(registry::find(day)?, &workspace_dir())?),
+        Command::RunProfiles { day, part, profile, format } => {
+            let runner = registry::find(day)?;
+            let dir = profiles::dir(runner.manifest_dir());
+            let profiles = match profile {
+                Some(name) => vec![profiles::Profile::load(&dir, &name, day)?],
+                None => profiles::Profile::load_all(&dir, day)?,
+            };
+            if profiles.is_empty() {
+                return Err(Error::Usage(format!("No profiles in {}", dir.display())));
+            }
+            let parts = match part {
+                Some(part) => vec![part],
+                None => vec![1, 2],
+            };
+            let rows = summary::run_profiles(runner, &profiles, &parts);
+            println!("{}", summary::write(format, &rows));
+            for warning in summary::warnings(&rows) {
+                eprintln!("Warning: {}", warning);
+            }
+            let mut failing = rows.iter()
+                .filter(|row| row.check.is_regression())
+                .filter_map(|row| row.profile.clone())
+                .collect::<Vec<_>>();
+            failing.dedup();
+            if !failing.is_empty() {
+                return Err(Error::Profiles(failing));
+            }
+            Ok(())
+        }
+        Command::Verify { answers, accept } => {
+            let path = answers.unwrap_or_else(answers::default_path);
+            let mut answers = answers::Answers::load(&path)?;
+            let checks = verify::verify(registry::DAYS, &answers);
+            for check in &checks {
+                println!("{}", check);
+            }
+            if accept {
+                for check in &checks {
+                    if let Some(answer) = &check.answer {
+                        answers.set(check.day, check.part, answer.clone());
+                    }
+                }
+                answers.save(&path)?;
+                println!("Accepted answers saved to {}", path.display());
+                return Ok(());
+            }
+            let regressions = checks.iter().filter(|check| check.is_regression()).count();
+            if regressions > 0 {
+                return Err(Error::Regression(regressions));
+            }
+            Ok(())
+        }
+        Command::Fetch { day, server } => {
+            let cache_dir = aoc_common::input::resolve::cache_dir()
+                .ok_or_else(|| Error::Usage("No user cache directory, set $XDG_CACHE_HOME".to_string()))?;
+            match client::Client::from_env(server)?.fetch(day, &cache_dir)? {
+                client::Fetched::Cached(path) => println!("Already cached at {}", path.display()),
+                client::Fetched::Downloaded(path) => println!("Saved to {}", path.display()),
+            }
+            Ok(())
+        }
+        Command::Submit { day, part, input, server } => {
+            let runner = registry::find(day)?;
+            let path = Resolver::from_env(input.as_deref(), runner.manifest_dir()).resolve(day)?;
+            let answer = if is_stdin(&path) {
+                runner.stream(&mut std::io::stdin().lock(), &[part])?
+            } else {
+                runner.run(&Input::read(path)?, &[part])?
+            }
+                .remove(0);
+            let log = submit::default_path()
+                .ok_or_else(|| Error::Usage("No user cache directory, set $XDG_CACHE_HOME".to_string()))?;
+            println!("Submitting {} for day {} part {}", answer, day, part);
+            let reply = submit::submit(&client::Client::from_env(server)?, &log, day, part, &answer, submit::now())?;
+            println!("{}", reply);
+            Ok(())
+        }
+        Command::Examples { day, page, server } => {
+            let runner = registry::find(day)?;
+            let html = match page {
+                Some(page) => read_input(page)?,
+                None => {
+                    let client = client::Client::from_env(server)?;
+                    let url = client.day_url(day, "");
+                    let response = client.send("GET", &url, None)?;
+                    if !response.is_success() {
+                        return Err(Error::Http(format!("GET {} failed with status {}", url, response.status)));
+                    }
+                    response.body
+                }
+            };
+            let examples = page::extract(&html);
+            if examples.is_empty() {
+                return Err(Error::Usage("The page has no example with a highlighted answer".to_string()));
+            }
+            let dir = aoc_common::example::dir(runner.manifest_dir());
+            for example in examples {
+                for path in example.save(&dir)? {
+                    println!("Wrote {}", path.display());
+                }
+            }
+            Ok(())
+        }
+        Command::Inputs { action, day } => {
+            let runners = match day {
+                Some(day) => vec![registry::find(day)?],
+                None => registry::DAYS.to_vec(),
+            };
+            let (key, created) = inputs::key(action)?;
+            if let Some(path) = created {
+                println!("Generated a new input key in {}, share it with the team", path.display());
+            }
+            for runner in runners {
+                for plain in inputs::plain_paths(runner.manifest_dir())? {
+                    match inputs::apply(action, &plain, &key)? {
+                        inputs::Outcome::Written(path) => println!("Wrote {}", path.display()),
+                        inputs::Outcome::Unchanged(path) => println!("{} is up to date", path.display()),
+                        inputs::Outcome::Missing(path) => println!("Day {}: no {}", runner.day(), path.display()),
+                    }
+                }
+            }
+            Ok(())
+        }
+        Command::New { day } => {
+            for path in scaffold::new_day(&workspace_dir(), day)? {
+                println!("Wrote {}", path.display());
+            }
+            Ok(())
+        }
+        Command::Bench { day, options, save, baseline } => {
+            let days = match day {
+                Some(day) => vec![registry::find(day)?],
+                None => registry::DAYS.to_vec(),
+            };
+            let mut phases = Vec::new();
+            for day in days {
+                let path = Resolver::from_env(None, day.manifest_dir()).resolve(day.day())?;
+                phases.extend(bench::bench(day, &Input::read(path)?, options)?);
+            }
+            let baseline = baseline.map(|path| bench::Baseline::load(&path)).transpose()?;
+            println!("{}", bench::Report { phases: &phases, baseline: baseline.as_ref() });
+            if let Some(path) = save {
+                bench::Baseline::from_phases(&phases).save(&path)?;
+                println!("Baseline saved to {}", path.display());
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use aoc_common::{Answer, Example};
+
+// Pulls the examples out of a puzzle page. Each part is an <article>: its example is
+// the last <pre><code> block in it, or the previous part's one when it has none, and
+// its expected answer is the last highlighted <code><em> in it.
+pub(crate) fn extract(html: &str) -> Vec<Example> {
+    let mut examples = Vec::<Example>::new();
+    for (index, article) in elements(html, "article").into_iter().take(2).enumerate() {
+        let part = index as u8 + 1;
+        let Some(answer) = highlighted(article) else {
+            continue;
+        };
+        let input = elements(article, "pre")
+            .into_iter()
+            .rev()
+            .find_map(|pre| elements(pre, "code").first().map(|code| text(code)));
+        match input {
+            Some(input) if examples.last().is_none_or(|example| example.input != input) => {
+                examples.push(Example {
+                    name: format!("example{}", examples.len() + 1),
+                    input,
+                    expected: vec![(part, answer)],
+                });
+            }
+            _ => {
+                if let Some(example) = examples.last_mut() {
+                    example.expected.push((part, answer));
+                }
+            }
+        }
+    }
+    examples
+}
+
+// The contents of each '<tag ...>...</tag>' element, in order.
+fn elements<'a>(html: &'a str, tag: &str) -> Vec<&'a str> {
+    let open = format!("<{}", tag);
+    let close = format!("</{}>", tag);
+    let mut found = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find(&open) {
+        let after = &rest[start + open.len()..];
+        // '<pre' mustn't match '<prefix>'.
+        if !after.starts_with(['>', ' ', '\t', '\n']) {
+            rest = after;
+            continue;
+        }
+        let Some(content_start) = after.find('>') else {
+            break;
+        };
+        let content = &after[content_start + 1..];
+        let Some(end) = content.find(&close) else {
+            break;
+        };
+        found.push(&content[..end]);
+        rest = &content[end + close.len()..];
+    }
+    found
+}
+
+fn highlighted(article: &str) -> Option<Answer> {
+    let in_code = elements(article, "code").into_iter()
+        .filter_map(|code| code.trim().strip_prefix("<em>")?.strip_suffix("</em>"))
+        .map(|em| (em.as_ptr() as usize, em));
+    let in_em = elements(article, "em").into_iter()
+        .filter_map(|em| em.trim().strip_prefix("<code>")?.strip_suffix("</code>"))
+        .map(|code| (code.as_ptr() as usize, code));
+    let (_, answer) = in_code.chain(in_em).max_by_key(|(position, _)| *position)?;
+    let Ok(answer) = text(answer).trim().parse::<Answer>();
+    Some(answer)
+}
+
+fn text(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    decode_entities(&text)
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest.find(';').map(|end| &rest[1..end]);
+        let c = entity.and_then(|entity| match entity {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X"))
+                .map_or_else(|| entity.strip_prefix('#')?.parse::<u32>().ok(), |hex| u32::from_str_radix(hex, 16).ok())
+                .and_then(char::from_u32),
+        });
+        match (c, entity) {
+            (Some(c), Some(entity)) => {
+                decoded.push(c);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PAGE: &str = "\
+<html><body><main>
+<article class=\"day-desc\"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+</code></pre>
+<p>Adding these together produces <code><em>142</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>53080</code>.</p>
+<article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2>
+<pre><code>two1nine
+<em>eight</em>wothree
+</code></pre>
+<p>Adding these together produces <em><code>281</code></em>.</p>
+</article>
+</main></body></html>";
+
+    #[test]
+    fn test_extract() {
+        assert_eq!(
+            vec![
+                Example { name: "example1".to_string(), input: "1abc2\npqr3stu8vwx\n".to_string(), expected: vec![(1, Answer::Unsigned(142))] },
+                Example { name: "example2".to_string(), input: "two1nine\neightwothree\n".to_string(), expected: vec![(2, Answer::Unsigned(281))] },
+            ],
+            extract(PAGE)
+        );
+    }
+
+    #[test]
+    fn test_extract_shared_example() {
+        let page = "\
+<article><pre><code>Game 1: 3 blue\n</code></pre><p>Sum <code><em>8</em></code>.</p></article>
+<article><p>Power <code><em>2286</em></code>.</p></article>";
+        assert_eq!(
+            vec![Example {
+                name: "example1".to_string(),
+                input: "Game 1: 3 blue\n".to_string(),
+                expected: vec![(1, Answer::Unsigned(8)), (2, Answer::Unsigned(2286))],
+            }],
+            extract(page)
+        );
+        assert!(extract("<html>Please log in</html>").is_empty());
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!("a < b && c > \"d\" '", decode_entities("a &lt; b &amp;&amp; c &gt; &quot;d&quot; &#39;"));
+        assert_eq!("R&D; &unknown;", decode_entities("R&D; &unknown;"));
+        assert_eq!("é", decode_entities("&#xE9;"));
+    }
+}
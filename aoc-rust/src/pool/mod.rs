@@ -0,0 +1,52 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+// Applies `f` to every item on a scoped pool of worker threads, keeping the items' order.
+pub(crate) fn map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let threads = std::thread::available_parallelism()
+        .map_or(1, usize::from)
+        .min(items.len());
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new((0..items.len()).map(|_| None).collect::<Vec<Option<R>>>());
+    std::thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                let result = f(item);
+                results.lock().unwrap_or_else(|error| error.into_inner())[index] = Some(result);
+            });
+        }
+    });
+    results.into_inner()
+        .unwrap_or_else(|error| error.into_inner())
+        .into_iter()
+        .map(|result| result.expect("Every item should have been mapped"))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use super::*;
+
+    #[test]
+    fn test_map() {
+        let items = (0..100).collect::<Vec<u64>>();
+        assert_eq!(items.iter().map(|item| item * item).collect::<Vec<_>>(), map(&items, |item| item * item));
+        assert!(map(&[] as &[u64], |item| *item).is_empty());
+    }
+
+    #[test]
+    fn test_map_uses_threads() {
+        let items = (0..64).collect::<Vec<u64>>();
+        let threads = map(&items, |_| {
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            std::thread::current().id()
+        });
+        let expected = std::thread::available_parallelism().map_or(1, usize::from).min(2);
+        assert!(threads.into_iter().collect::<HashSet<_>>().len() >= expected);
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use aoc_common::input::seal;
+use crate::answers::Answers;
+
+pub(crate) const DIR: &str = "profiles";
+
+const INPUT_EXTENSION: &str = "txt";
+const EXPECTED_EXTENSION: &str = "expected";
+
+pub(crate) fn dir(manifest_dir: &str) -> PathBuf {
+    Path::new(manifest_dir).join("src").join("input").join(DIR)
+}
+
+// Another account's input: '<name>.txt', or its sealed '<name>.txt.enc', and the answers
+// it's known to give in '<name>.expected', in the same format as the examples'.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Profile {
+    pub(crate) name: String,
+    pub(crate) input: PathBuf,
+    pub(crate) expected: Answers,
+}
+
+// A name may hold dots of its own, like 'alice.v2', so the extension is appended.
+pub(crate) fn plain_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{}.{}", name, INPUT_EXTENSION))
+}
+
+impl Profile {
+    pub(crate) fn load(dir: &Path, name: &str, day: u8) -> aoc_common::Result<Self> {
+        let plain = plain_path(dir, name);
+        let sealed = seal::sealed_path(&plain);
+        let input = match (plain.exists(), sealed.exists()) {
+            (true, _) => plain,
+            (false, true) => sealed,
+            (false, false) => return Err(aoc_common::Error::InputNotFound { day, tried: vec![plain, sealed] }),
+        };
+        let mut expected = Answers::default();
+        let expected_path = dir.join(format!("{}.{}", name, EXPECTED_EXTENSION));
+        if expected_path.exists() {
+            for (part, answer) in aoc_common::example::parse_expected(&aoc_common::read_input(expected_path)?)? {
+                expected.set(day, part, answer);
+            }
+        }
+        Ok(Self { name: name.to_string(), input, expected })
+    }
+
+    pub(crate) fn load_all(dir: &Path, day: u8) -> aoc_common::Result<Vec<Self>> {
+        names(dir)?.iter()
+            .map(|name| Self::load(dir, name, day))
+            .collect()
+    }
+}
+
+// The names of the profiles that have an input, plain or sealed; a missing directory has none.
+pub(crate) fn names(dir: &Path) -> aoc_common::Result<BTreeSet<String>> {
+    if !dir.is_dir() {
+        return Ok(BTreeSet::new());
+    }
+    let io_error = |source| aoc_common::Error::Io { path: dir.to_path_buf(), source };
+    let mut names = BTreeSet::new();
+    for entry in std::fs::read_dir(dir).map_err(io_error)? {
+        let file_name = entry.map_err(io_error)?.file_name();
+        let file_name = file_name.to_string_lossy();
+        let file_name = file_name.strip_suffix(&format!(".{}", seal::EXTENSION)).unwrap_or(&file_name);
+        if let Some(name) = file_name.strip_suffix(&format!(".{}", INPUT_EXTENSION)) {
+            names.insert(name.to_string());
+        }
+    }
+    Ok(names)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::Answer;
+
+    #[test]
+    fn test_load_all() {
+        let dir = std::env::temp_dir().join(format!("aoc-profiles-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(Profile::load_all(&dir, 1).unwrap().is_empty());
+
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("bob.txt.enc"), "sealed").unwrap();
+        std::fs::write(dir.join("alice.txt"), "1abc2\n").unwrap();
+        std::fs::write(dir.join("alice.expected"), "part1 = 12\n").unwrap();
+        std::fs::write(dir.join("alice.v2.txt"), "two1\n").unwrap();
+        std::fs::write(dir.join("alice.v2.expected"), "part1 = 11\n").unwrap();
+        std::fs::write(dir.join("notes.md"), "").unwrap();
+
+        let profiles = Profile::load_all(&dir, 1).unwrap();
+        assert_eq!(vec!["alice", "alice.v2", "bob"], profiles.iter().map(|profile| profile.name.as_str()).collect::<Vec<_>>());
+        assert_eq!(dir.join("alice.txt"), profiles[0].input);
+        assert_eq!(Some(&Answer::Unsigned(12)), profiles[0].expected.get(1, 1));
+        assert_eq!(None, profiles[0].expected.get(1, 2));
+        assert_eq!(dir.join("alice.v2.txt"), profiles[1].input);
+        assert_eq!(Some(&Answer::Unsigned(11)), profiles[1].expected.get(1, 1));
+        assert_eq!(dir.join("bob.txt.enc"), profiles[2].input);
+        assert_eq!(Answers::default(), profiles[2].expected);
+
+        assert!(matches!(Profile::load(&dir, "carol", 1), Err(aoc_common::Error::InputNotFound { .. })));
+        std::fs::write(dir.join("alice.expected"), "part3 = 1\n").unwrap();
+        assert!(matches!(Profile::load(&dir, "alice", 1), Err(aoc_common::Error::Parse(_))));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,44 @@
+use aoc_common::Runner;
+use crate::error::{Error, Result};
+
+pub(crate) const DAYS: &[&dyn Runner] = &[
+    &aoc_01::Day01,
+    &aoc_02::Day02,
+    &aoc_03::Day03,
+    &aoc_04::Day04,
+];
+
+pub(crate) fn find(day: u8) -> Result<&'static dyn Runner> {
+    DAYS.iter()
+        .find(|registered| registered.day() == day)
+        .copied()
+        .ok_or(Error::UnknownDay(day))
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_common::{Answer, Input};
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        assert_eq!(3, find(3).unwrap().day());
+        assert!(matches!(find(25), Err(Error::UnknownDay(25))));
+    }
+
+    #[test]
+    fn test_days_are_unique() {
+        for (index, day) in DAYS.iter().enumerate() {
+            assert!(DAYS[index + 1..].iter().all(|other| other.day() != day.day()));
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        assert_eq!(vec![Answer::Unsigned(50)], find(1).unwrap().run(&Input::from("1abc2\npqr3stu8vwx"), &[1]).unwrap());
+        assert!(matches!(
+            find(4).unwrap().run(&Input::from("Card 1: 1 | 1"), &[2]),
+            Err(aoc_common::Error::Unsolved { day: 4, part: 2 })
+        ));
+    }
+}
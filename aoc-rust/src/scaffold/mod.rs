@@ -0,0 +1,166 @@
+use std::path::{Path, PathBuf};
+use crate::answers::{self, Answers};
+use crate::error::{Error, Result};
+
+const BUILD_RS: &str = include_str!("template/build.rs.template");
+const CARGO_TOML: &str = include_str!("template/Cargo.toml.template");
+const GITIGNORE: &str = include_str!("template/gitignore.template");
+const MAIN_RS: &str = include_str!("template/main.rs.template");
+const LIB_RS: &str = include_str!("template/lib.rs.template");
+
+const REGISTRY: &str = "aoc-rust/src/registry/mod.rs";
+const RUNNER_MANIFEST: &str = "aoc-rust/Cargo.toml";
+
+pub(crate) fn crate_dir_name(day: u8) -> String {
+    format!("aoc-{:02}-rust", day)
+}
+
+fn render(template: &str, day: u8) -> String {
+    template
+        .replace("{{DAY}}", &format!("{:02}", day))
+        .replace("{{DAY_NUMBER}}", &day.to_string())
+}
+
+// Inserts `line` right before the first `closing` line that follows `opening`.
+fn insert_before_closing(text: &str, opening: &str, closing: &str, line: &str) -> Option<String> {
+    let start = text.find(opening)? + opening.len();
+    let end = start + text[start..].find(&format!("\n{}", closing))? + 1;
+    Some(format!("{}{}\n{}", &text[..end], line, &text[end..]))
+}
+
+fn read(path: &Path) -> Result<String> {
+    Ok(aoc_common::read_input(path)?)
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    std::fs::write(path, contents)
+        .map_err(|source| aoc_common::Error::Io { path: path.to_path_buf(), source }.into())
+}
+
+// Dependencies are the last section of the runner's manifest.
+fn append_dependency(text: &str, line: &str) -> Option<String> {
+    text.contains("[dependencies]")
+        .then(|| format!("{}\n{}\n", text.trim_end(), line))
+}
+
+fn edit(root: &Path, file: &str, edit: impl Fn(&str) -> Option<String>) -> Result<(PathBuf, String)> {
+    let path = root.join(file);
+    let edited = edit(&read(&path)?)
+        .ok_or_else(|| Error::Scaffold(format!("Couldn't find where to register the day in {}", path.display())))?;
+    Ok((path, edited))
+}
+
+pub(crate) fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        return Err(Error::Usage(format!("Bad day '{}', expected 1 to 25", day)));
+    }
+    let crate_name = crate_dir_name(day);
+    let crate_dir = root.join(&crate_name);
+    if crate_dir.exists() {
+        return Err(Error::Scaffold(format!("{} already exists", crate_dir.display())));
+    }
+
+    // Every edit is prepared before anything is written, so that a failure leaves the tree untouched.
+    let edits = [
+        edit(root, "Cargo.toml", |text| {
+            insert_before_closing(text, "members = [", "]", &format!("    \"{}\",", crate_name))
+        })?,
+        edit(root, RUNNER_MANIFEST, |text| {
+            append_dependency(text, &render("aoc-{{DAY}} = { path = \"../aoc-{{DAY}}-rust\" }", day))
+        })?,
+        edit(root, REGISTRY, |text| {
+            insert_before_closing(text, "pub(crate) const DAYS: &[&dyn Runner] = &[", "];", &render("    &aoc_{{DAY}}::Day{{DAY}},", day))
+        })?,
+    ];
+    let answers_path = root.join(answers::FILE_NAME);
+    let mut answers = Answers::load(&answers_path)?;
+    answers.add_day(day);
+
+    let files = [
+        (crate_dir.join("Cargo.toml"), render(CARGO_TOML, day)),
+        (crate_dir.join(".gitignore"), GITIGNORE.to_string()),
+        (crate_dir.join("build.rs"), BUILD_RS.to_string()),
+        (crate_dir.join("src").join("main.rs"), render(MAIN_RS, day)),
+        (crate_dir.join("src").join("lib.rs"), render(LIB_RS, day)),
+    ];
+    // 'aoc examples' fills it in; the build script generates a test per example found there.
+    let examples_dir = crate_dir.join("src").join("input").join(aoc_common::example::DIR);
+    std::fs::create_dir_all(&examples_dir)
+        .map_err(|source| aoc_common::Error::Io { path: examples_dir, source })?;
+
+    let mut written = Vec::new();
+    for (path, contents) in files.into_iter().chain(edits) {
+        write(&path, &contents)?;
+        written.push(path);
+    }
+    answers.save(&answers_path)?;
+    written.push(answers_path);
+    Ok(written)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const WORKSPACE: &str = "[workspace]\nmembers = [\n    \"aoc-rust\",\n    \"aoc-01-rust\",\n]\n";
+    const MANIFEST: &str = "[package]\nname = \"aoc\"\n\n[dependencies]\naoc-01 = { path = \"../aoc-01-rust\" }\n";
+    const REGISTRY_RS: &str = "pub(crate) const DAYS: &[&dyn Runner] = &[\n    &aoc_01::Day01,\n];\n\nfn find() {}\n";
+
+    fn fake_workspace(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-test-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("aoc-rust/src/registry")).unwrap();
+        std::fs::write(root.join("Cargo.toml"), WORKSPACE).unwrap();
+        std::fs::write(root.join(RUNNER_MANIFEST), MANIFEST).unwrap();
+        std::fs::write(root.join(REGISTRY), REGISTRY_RS).unwrap();
+        root
+    }
+
+    #[test]
+    fn test_insert_before_closing() {
+        assert_eq!(
+            Some("a = [\n    1,\n    2,\n]\n".to_string()),
+            insert_before_closing("a = [\n    1,\n]\n", "a = [", "]", "    2,")
+        );
+        assert_eq!(None, insert_before_closing("a = [\n    1,\n", "a = [", "]", "    2,"));
+        assert_eq!(None, insert_before_closing("b = [\n]\n", "a = [", "]", "    2,"));
+    }
+
+    #[test]
+    fn test_render() {
+        assert_eq!("aoc_07::Day07 = 7", render("aoc_{{DAY}}::Day{{DAY}} = {{DAY_NUMBER}}", 7));
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = fake_workspace("new");
+        new_day(&root, 5).unwrap();
+
+        let read = |path: &str| std::fs::read_to_string(root.join(path)).unwrap();
+        assert_eq!("[workspace]\nmembers = [\n    \"aoc-rust\",\n    \"aoc-01-rust\",\n    \"aoc-05-rust\",\n]\n", read("Cargo.toml"));
+        assert!(read(RUNNER_MANIFEST).ends_with("aoc-01 = { path = \"../aoc-01-rust\" }\naoc-05 = { path = \"../aoc-05-rust\" }\n"));
+        assert!(read(REGISTRY).starts_with("pub(crate) const DAYS: &[&dyn Runner] = &[\n    &aoc_01::Day01,\n    &aoc_05::Day05,\n];\n"));
+        assert!(read("answers.toml").ends_with("[day05]\n"));
+        assert!(read("aoc-05-rust/Cargo.toml").contains("name = \"aoc-05\""));
+        assert!(read("aoc-05-rust/src/lib.rs").contains("const DAY: u8 = 5;"));
+        assert!(read("aoc-05-rust/src/main.rs").contains("&Day05"));
+        assert!(read("aoc-05-rust/src/lib.rs").ends_with("aoc_common::example_tests!(Day05);\n"));
+        assert!(read("aoc-05-rust/build.rs").contains("generate_tests()"));
+        assert!(root.join("aoc-05-rust/src/input/examples").is_dir());
+
+        assert!(matches!(new_day(&root, 5), Err(Error::Scaffold(_))));
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_new_day_errors() {
+        let root = fake_workspace("errors");
+        assert!(matches!(new_day(&root, 26), Err(Error::Usage(_))));
+
+        std::fs::write(root.join(REGISTRY), "fn find() {}\n").unwrap();
+        assert!(matches!(new_day(&root, 6), Err(Error::Scaffold(_))));
+        assert!(!root.join("aoc-06-rust").exists());
+        assert_eq!(WORKSPACE, std::fs::read_to_string(root.join("Cargo.toml")).unwrap());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
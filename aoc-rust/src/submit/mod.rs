@@ -0,0 +1,390 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use aoc_common::Answer;
+use crate::client::Client;
+use crate::error::{Error, Result};
+use crate::toml;
+
+pub(crate) const FILE_NAME: &str = "submissions.toml";
+
+pub(crate) fn default_path() -> Option<PathBuf> {
+    aoc_common::input::resolve::cache_dir().map(|dir| dir.join(FILE_NAME))
+}
+
+pub(crate) fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    TooSoon,
+    AlreadySolved,
+    Unknown,
+}
+
+const VERDICTS: [(Verdict, &str); 7] = [
+    (Verdict::Right, "right"),
+    (Verdict::Wrong, "wrong"),
+    (Verdict::TooHigh, "too high"),
+    (Verdict::TooLow, "too low"),
+    (Verdict::TooSoon, "too soon"),
+    (Verdict::AlreadySolved, "already solved"),
+    (Verdict::Unknown, "unknown"),
+];
+
+impl Verdict {
+    fn name(self) -> &'static str {
+        VERDICTS.iter().find(|(verdict, _)| *verdict == self).map_or("unknown", |(_, name)| name)
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        VERDICTS.iter().find(|(_, known)| *known == name).map(|(verdict, _)| *verdict)
+    }
+
+    pub(crate) fn is_rejection(self) -> bool {
+        matches!(self, Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow)
+    }
+}
+
+// What the server made of an answer, and how long it wants us to wait before the next one.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Reply {
+    pub(crate) verdict: Verdict,
+    pub(crate) wait: Option<u64>,
+    pub(crate) message: String,
+}
+
+impl Reply {
+    pub(crate) fn parse(body: &str) -> Self {
+        let message = main_text(body);
+        let verdict = if message.contains("That's the right answer") {
+            Verdict::Right
+        } else if message.contains("That's not the right answer") {
+            if message.contains("too high") {
+                Verdict::TooHigh
+            } else if message.contains("too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            }
+        } else if message.contains("You gave an answer too recently") {
+            Verdict::TooSoon
+        } else if message.contains("You don't seem to be solving the right level") {
+            Verdict::AlreadySolved
+        } else {
+            Verdict::Unknown
+        };
+        Self { verdict, wait: parse_wait(&message), message }
+    }
+}
+
+impl fmt::Display for Reply {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+// The reply is a whole page; only the text of its <article> matters.
+fn main_text(body: &str) -> String {
+    let article = body.find("<article")
+        .and_then(|start| body[start..].find("</article>").map(|end| &body[start..start + end]))
+        .unwrap_or(body);
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Understands "you have 1m 30s left to wait" and "please wait 5 minutes before trying again".
+fn parse_wait(message: &str) -> Option<u64> {
+    if let Some(end) = message.find(" left to wait") {
+        let start = message[..end].rfind("have ").map_or(0, |start| start + 5);
+        return message[start..end].split_whitespace()
+            .map(|amount| {
+                let (number, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+                let scale = match unit {
+                    "h" => 3600,
+                    "m" => 60,
+                    "s" => 1,
+                    _ => return None,
+                };
+                number.parse::<u64>().ok().map(|number| number * scale)
+            })
+            .sum();
+    }
+    let lower = message.to_lowercase();
+    let start = lower.find("please wait ")? + "please wait ".len();
+    let mut words = lower[start..].split_whitespace();
+    let number = match words.next()? {
+        "one" | "a" => 1,
+        word => word.parse::<u64>().ok()?,
+    };
+    match words.next()?.trim_end_matches(|c: char| !c.is_alphabetic()) {
+        "minute" | "minutes" => Some(number * 60),
+        "second" | "seconds" => Some(number),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Submission {
+    pub(crate) day: u8,
+    pub(crate) part: u8,
+    pub(crate) answer: Answer,
+    pub(crate) verdict: Verdict,
+    pub(crate) at: u64,
+    pub(crate) retry_at: Option<u64>,
+}
+
+// Every answer sent, appended as one '[dayNN.partP]' section per submission.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Submissions {
+    records: Vec<Submission>,
+}
+
+impl Submissions {
+    pub(crate) fn load(path: &Path) -> aoc_common::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Self::parse(&aoc_common::read_input(path)?)
+    }
+
+    pub(crate) fn parse(text: &str) -> aoc_common::Result<Self> {
+        let mut records = Vec::new();
+        for section in toml::parse(text)? {
+            let (day, part) = section.name.strip_prefix("day")
+                .and_then(|name| name.split_once(".part"))
+                .and_then(|(day, part)| Some((day.parse::<u8>().ok()?, part.parse::<u8>().ok()?)))
+                .ok_or_else(|| section.error("a '[dayNN.partP]' header"))?;
+            let value = |key: &str| section.entries.iter().find(|entry| entry.key == key);
+            let required = |key: &str| value(key).ok_or_else(|| section.error(&format!("an '{}' entry", key)));
+            let number = |key: &str| value(key)
+                .map(|entry| entry.value.parse::<u64>().map_err(|_| entry.error("a number")))
+                .transpose();
+            let Ok(answer) = required("answer")?.value.parse::<Answer>();
+            let verdict = required("verdict")?;
+            records.push(Submission {
+                day,
+                part,
+                answer,
+                verdict: Verdict::from_name(&verdict.value).ok_or_else(|| verdict.error("a known verdict"))?,
+                at: number("at")?.ok_or_else(|| section.error("an 'at' entry"))?,
+                retry_at: number("retry_at")?,
+            });
+        }
+        Ok(Self { records })
+    }
+
+    pub(crate) fn append(&mut self, path: &Path, submission: Submission) -> aoc_common::Result<()> {
+        let io_error = |source| aoc_common::Error::Io { path: path.to_path_buf(), source };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(io_error)?;
+        std::io::Write::write_all(&mut file, section(&submission).as_bytes()).map_err(io_error)?;
+        self.records.push(submission);
+        Ok(())
+    }
+
+    // Refuses what the server would reject anyway: answers during a cooldown, answers
+    // to solved parts, and answers already known to be wrong. A wrong level reply
+    // doesn't refuse anything: it also comes for part 2 before part 1 is solved.
+    pub(crate) fn check(&self, day: u8, part: u8, answer: &Answer, now: u64) -> Result<()> {
+        if let Some(retry_at) = self.records.iter().filter_map(|record| record.retry_at).max() {
+            if retry_at > now {
+                return Err(Error::Refused(format!("The server asked to wait {}s before the next answer", retry_at - now)));
+            }
+        }
+        let previous = self.records.iter().filter(|record| record.day == day && record.part == part);
+        for record in previous {
+            let refuse = |reason: String| Err(Error::Refused(format!("Not sending {}: {}", answer, reason)));
+            match record.verdict {
+                Verdict::Right => return refuse(format!("day {} part {} is already solved", day, part)),
+                verdict if verdict.is_rejection() && &record.answer == answer => return refuse("it was already rejected".to_string()),
+                Verdict::TooHigh if compare(answer, &record.answer).is_some_and(|order| order.is_ge()) => {
+                    return refuse(format!("{} was already too high", record.answer));
+                }
+                Verdict::TooLow if compare(answer, &record.answer).is_some_and(|order| order.is_le()) => {
+                    return refuse(format!("{} was already too low", record.answer));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+// Sends an answer unless the log says it's pointless, then records the server's reply.
+pub(crate) fn submit(client: &Client, log: &Path, day: u8, part: u8, answer: &Answer, now: u64) -> Result<Reply> {
+    let mut submissions = Submissions::load(log)?;
+    submissions.check(day, part, answer, now)?;
+    let response = client.submit(day, part, answer)?;
+    if !response.is_success() {
+        return Err(Error::Http(format!("Submitting failed with status {}: {}", response.status, main_text(&response.body))));
+    }
+    let reply = Reply::parse(&response.body);
+    submissions.append(log, Submission {
+        day,
+        part,
+        answer: answer.clone(),
+        verdict: reply.verdict,
+        at: now,
+        retry_at: reply.wait.map(|wait| now + wait),
+    })?;
+    Ok(reply)
+}
+
+fn section(submission: &Submission) -> String {
+    let mut entries = vec![
+        ("answer", toml::quoted(&submission.answer)),
+        ("verdict", toml::quoted(submission.verdict.name())),
+        ("at", submission.at.to_string()),
+    ];
+    if let Some(retry_at) = submission.retry_at {
+        entries.push(("retry_at", retry_at.to_string()));
+    }
+    let mut toml = String::new();
+    toml::write_section(&mut toml, &format!("day{:02}.part{}", submission.day, submission.part), &entries);
+    toml
+}
+
+fn compare(left: &Answer, right: &Answer) -> Option<std::cmp::Ordering> {
+    let value = |answer: &Answer| match answer {
+        Answer::Unsigned(value) => Some(*value as i128),
+        Answer::Signed(value) => Some(*value as i128),
+        _ => None,
+    };
+    Some(value(left)?.cmp(&value(right)?))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::http::test::{ok, serve};
+    use super::*;
+
+    fn page(article: &str) -> String {
+        format!("<html><body><main><article><p>{}</p></article></main></body></html>", article)
+    }
+
+    fn submission(part: u8, answer: u64, verdict: Verdict, retry_at: Option<u64>) -> Submission {
+        Submission { day: 1, part, answer: Answer::Unsigned(answer), verdict, at: 1000, retry_at }
+    }
+
+    #[test]
+    fn test_parse_reply() {
+        let reply = Reply::parse(&page("That's the right answer! You are <em>one gold star</em> closer."));
+        assert_eq!((Verdict::Right, None), (reply.verdict, reply.wait));
+        assert_eq!("That's the right answer! You are one gold star closer.", reply.message);
+
+        let reply = Reply::parse(&page("That's not the right answer; your answer is too high. Please wait one minute before trying again."));
+        assert_eq!((Verdict::TooHigh, Some(60)), (reply.verdict, reply.wait));
+        let reply = Reply::parse(&page("That's not the right answer; your answer is too low. Please wait 5 minutes before trying again."));
+        assert_eq!((Verdict::TooLow, Some(300)), (reply.verdict, reply.wait));
+        let reply = Reply::parse(&page("That's not the right answer. If you're stuck, please wait 30 seconds."));
+        assert_eq!((Verdict::Wrong, Some(30)), (reply.verdict, reply.wait));
+        let reply = Reply::parse(&page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 30s left to wait."));
+        assert_eq!((Verdict::TooSoon, Some(90)), (reply.verdict, reply.wait));
+        let reply = Reply::parse(&page("You don't seem to be solving the right level.  Did you already complete it?"));
+        assert_eq!((Verdict::AlreadySolved, None), (reply.verdict, reply.wait));
+        assert_eq!(Verdict::Unknown, Reply::parse("Internal error").verdict);
+    }
+
+    #[test]
+    fn test_parse_submissions() {
+        let text = "\n[day01.part1]\nanswer = \"142\"\nverdict = \"too low\"\nat = 1000\nretry_at = 1060\n";
+        let submissions = Submissions::parse(text).unwrap();
+        assert_eq!(vec![submission(1, 142, Verdict::TooLow, Some(1060))], submissions.records);
+        assert_eq!(text, section(&submissions.records[0]));
+
+        assert!(Submissions::parse("[day01]\nanswer = \"1\"\nverdict = \"right\"\nat = 1").is_err());
+        assert!(Submissions::parse("[day01.part1]\nanswer = \"1\"\nverdict = \"maybe\"\nat = 1").is_err());
+        assert!(Submissions::parse("[day01.part1]\nanswer = \"1\"\nverdict = \"right\"").is_err());
+    }
+
+    #[test]
+    fn test_append() {
+        let path = std::env::temp_dir().join(format!("aoc-submissions-{}.toml", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut submissions = Submissions::load(&path).unwrap();
+        submissions.append(&path, submission(1, 142, Verdict::TooLow, None)).unwrap();
+        submissions.append(&path, submission(1, 143, Verdict::Right, None)).unwrap();
+        assert_eq!(submissions, Submissions::load(&path).unwrap());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_check() {
+        let submissions = Submissions {
+            records: vec![
+                submission(1, 100, Verdict::TooLow, Some(1060)),
+                submission(1, 200, Verdict::TooHigh, None),
+                submission(1, 150, Verdict::Wrong, None),
+                submission(2, 7, Verdict::Right, None),
+                submission(3, 5, Verdict::AlreadySolved, None),
+            ],
+        };
+        let check = |part, answer: u64, now| submissions.check(1, part, &Answer::Unsigned(answer), now).is_ok();
+        assert!(!check(1, 120, 1059));
+        assert!(check(1, 120, 1060));
+        assert!(!check(1, 100, 1060));
+        assert!(!check(1, 90, 1060));
+        assert!(!check(1, 250, 1060));
+        assert!(!check(1, 150, 1060));
+        assert!(!check(2, 8, 1060));
+        assert!(check(3, 5, 1060));
+        assert!(submissions.check(1, 1, &Answer::Text("abc".to_string()), 1060).is_ok());
+    }
+
+    #[test]
+    fn test_submit() {
+        let (server, requests, handle) = serve(vec![
+            ok(&page("That's not the right answer; your answer is too low. Please wait one minute before trying again.")),
+            ok(&page("That's the right answer!")),
+        ]);
+        let client = Client::new(&server, "secret").unwrap();
+        let log = std::env::temp_dir().join(format!("aoc-submit-{}.toml", std::process::id()));
+        let _ = std::fs::remove_file(&log);
+
+        let reply = submit(&client, &log, 1, 2, &Answer::Unsigned(100), 1000).unwrap();
+        assert_eq!((Verdict::TooLow, Some(60)), (reply.verdict, reply.wait));
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /2023/day/1/answer HTTP/1.1\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=100"));
+
+        // Neither is sent: the cooldown hasn't passed, then the answer is known to be too low.
+        assert!(matches!(submit(&client, &log, 1, 2, &Answer::Unsigned(120), 1059), Err(Error::Refused(_))));
+        assert!(matches!(submit(&client, &log, 1, 2, &Answer::Unsigned(99), 1060), Err(Error::Refused(_))));
+
+        let reply = submit(&client, &log, 1, 2, &Answer::Unsigned(120), 1060).unwrap();
+        assert_eq!(Verdict::Right, reply.verdict);
+        handle.join().unwrap();
+        assert!(matches!(submit(&client, &log, 1, 2, &Answer::Unsigned(120), 2000), Err(Error::Refused(_))));
+
+        let submissions = Submissions::load(&log).unwrap();
+        assert_eq!(
+            vec![(Answer::Unsigned(100), Verdict::TooLow, Some(1060)), (Answer::Unsigned(120), Verdict::Right, None)],
+            submissions.records.iter()
+                .map(|record| (record.answer.clone(), record.verdict, record.retry_at))
+                .collect::<Vec<_>>()
+        );
+        std::fs::remove_file(&log).unwrap();
+    }
+}
@@ -0,0 +1,396 @@
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use aoc_common::{Answer, Input, Options, Runner};
+use aoc_common::input::hash::{sha256_hex, HashingReader};
+use aoc_common::input::resolve::Resolver;
+use aoc_common::solution::Parsed;
+use crate::answers::Answers;
+use crate::json;
+use crate::pool;
+use crate::profiles::Profile;
+use crate::verify::{self, Check, Status};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = ();
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(()),
+        }
+    }
+}
+
+// One part's result, with what's needed to tell where it came from.
+#[derive(Debug)]
+pub(crate) struct Row {
+    pub(crate) check: Check,
+    pub(crate) profile: Option<String>,
+    pub(crate) parse: Option<Duration>,
+    pub(crate) solve: Option<Duration>,
+    pub(crate) input_hash: Option<String>,
+    pub(crate) version: &'static str,
+    pub(crate) warnings: Vec<String>,
+}
+
+struct Prepared {
+    parsed: aoc_common::Result<Parsed>,
+    parse: Duration,
+    input_hash: String,
+}
+
+fn prepare(runner: &dyn Runner, input: &Input, options: &Options) -> Prepared {
+    let start = Instant::now();
+    let parsed = runner.parse_with(input, options);
+    Prepared { parsed, parse: start.elapsed(), input_hash: sha256_hex(input.as_bytes()) }
+}
+
+fn solve(runner: &dyn Runner, prepared: &aoc_common::Result<Prepared>, part: u8, answers: Option<&Answers>) -> Row {
+    let day = runner.day();
+    let (check, parse, solve, input_hash, warnings) = match prepared {
+        Ok(Prepared { parsed: Ok(parsed), parse, input_hash }) => {
+            let start = Instant::now();
+            let result = runner.solve(parsed, part);
+            let solve = result.is_ok().then(|| start.elapsed());
+            let warnings = if result.is_ok() { runner.warnings(parsed, part) } else { Vec::new() };
+            (verify::check(day, part, result, answers), Some(*parse), solve, Some(input_hash.clone()), warnings)
+        }
+        Ok(Prepared { parsed: Err(error), parse, input_hash }) => {
+            let check = Check { day, part, answer: None, status: Status::Failed(error.to_string()) };
+            (check, Some(*parse), None, Some(input_hash.clone()), Vec::new())
+        }
+        Err(error) => (verify::without_input(day, part, error, answers), None, None, None, Vec::new()),
+    };
+    Row { check, profile: None, parse, solve, input_hash, version: runner.version(), warnings }
+}
+
+pub(crate) fn run_day(runner: &dyn Runner, input: &Input, options: &Options, parts: &[u8], answers: Option<&Answers>) -> Vec<Row> {
+    let prepared = Ok(prepare(runner, input, options));
+    parts.iter()
+        .map(|part| solve(runner, &prepared, *part, answers))
+        .collect()
+}
+
+// A streamed input is parsed and solved in one go, so its rows have no timings or warnings.
+pub(crate) fn stream_day(runner: &dyn Runner, reader: &mut dyn BufRead, options: &Options, parts: &[u8], answers: Option<&Answers>) -> aoc_common::Result<Vec<Row>> {
+    let mut reader = HashingReader::new(reader);
+    let results = runner.stream_with(&mut reader, parts, options)?;
+    let input_hash = reader.finish_hex();
+    Ok(parts.iter()
+        .zip(results)
+        .map(|(part, answer)| Row {
+            check: verify::check(runner.day(), *part, Ok(answer), answers),
+            profile: None,
+            parse: None,
+            solve: None,
+            input_hash: Some(input_hash.clone()),
+            version: runner.version(),
+            warnings: Vec::new(),
+        })
+        .collect())
+}
+
+// Warnings are written apart from the rows, so that they don't change the records.
+pub(crate) fn warnings(rows: &[Row]) -> Vec<String> {
+    rows.iter()
+        .flat_map(|row| row.warnings.iter().map(move |warning| match &row.profile {
+            Some(profile) => format!("Day {} part {} ({}): {}", row.check.day, row.check.part, profile, warning),
+            None => format!("Day {} part {}: {}", row.check.day, row.check.part, warning),
+        }))
+        .collect()
+}
+
+// Parses every day's input, found by `resolver`, then solves every part, each step on the
+// thread pool.
+pub(crate) fn run_all(days: &[&dyn Runner], answers: &Answers, resolver: impl Fn(&dyn Runner) -> Resolver + Sync) -> Vec<Row> {
+    let prepared = pool::map(days, |runner| {
+        let input = resolver(*runner)
+            .resolve(runner.day())
+            .and_then(Input::read)?;
+        Ok(prepare(*runner, &input, &Options::default()))
+    });
+    let jobs = (0..days.len())
+        .flat_map(|index| [(index, 1), (index, 2)])
+        .collect::<Vec<_>>();
+    pool::map(&jobs, |&(index, part)| solve(days[index], &prepared[index], part, Some(answers)))
+}
+
+// Each profile is checked against its own expected answers rather than the accepted ones.
+pub(crate) fn run_profiles(runner: &dyn Runner, profiles: &[Profile], parts: &[u8]) -> Vec<Row> {
+    pool::map(profiles, |profile| {
+        let prepared = Input::read(&profile.input).map(|input| prepare(runner, &input, &Options::default()));
+        parts.iter()
+            .map(|part| Row { profile: Some(profile.name.clone()), ..solve(runner, &prepared, *part, Some(&profile.expected)) })
+            .collect::<Vec<_>>()
+    })
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+pub(crate) fn write(format: Format, rows: &[Row]) -> String {
+    match format {
+        Format::Text => Table(rows).to_string(),
+        Format::Json => to_json(rows),
+        Format::Csv => to_csv(rows),
+    }
+}
+
+fn duration(duration: Option<Duration>) -> String {
+    duration.map_or_else(|| "-".to_string(), |duration| format!("{:.1?}", duration))
+}
+
+pub(crate) struct Table<'a>(pub(crate) &'a [Row]);
+
+impl fmt::Display for Table<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.0.iter()
+            .filter_map(|row| row.check.answer.as_ref())
+            .map(|answer| answer.to_string().len())
+            .fold("Answer".len(), usize::max);
+        // The profile column is only there when some row came from one.
+        let profile_width = self.0.iter()
+            .filter_map(|row| row.profile.as_ref())
+            .map(String::len)
+            .fold(None, |width, len| Some(usize::max(width.unwrap_or("Profile".len()), len)));
+        let profile = |name: &str| profile_width.map_or_else(String::new, |width| format!("{:<width$}  ", name));
+        write!(f, "{:>3}  {:>4}  {}{:<width$}  {:>10}  {:>10}  Status", "Day", "Part", profile("Profile"), "Answer", "Parse", "Solve")?;
+        for row in self.0 {
+            let check = &row.check;
+            let answer = check.answer.as_ref().map_or_else(|| "-".to_string(), ToString::to_string);
+            write!(
+                f,
+                "\n{:>3}  {:>4}  {}{:<width$}  {:>10}  {:>10}  {}",
+                check.day,
+                check.part,
+                profile(row.profile.as_deref().unwrap_or("-")),
+                answer,
+                duration(row.parse),
+                duration(row.solve),
+                check.status.label(),
+            )?;
+        }
+        // Reasons are too long for the table, so they follow it.
+        for row in self.0 {
+            if let Status::Skipped(reason) | Status::Failed(reason) = &row.check.status {
+                match &row.profile {
+                    Some(profile) => write!(f, "\n\nDay {} part {} ({}): {}", row.check.day, row.check.part, profile, reason)?,
+                    None => write!(f, "\n\nDay {} part {}: {}", row.check.day, row.check.part, reason)?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) const FIELDS: [&str; 11] = [
+    "day", "part", "profile", "answer", "parse_ns", "solve_ns", "input_sha256", "version", "status", "expected", "reason",
+];
+
+// A record's values in the order of FIELDS, flagged when they're numbers, which JSON leaves unquoted.
+fn values(row: &Row) -> [(Option<String>, bool); 11] {
+    let check = &row.check;
+    let (expected, reason) = match &check.status {
+        Status::Changed { expected } => (Some(expected.to_string()), None),
+        Status::Skipped(reason) | Status::Failed(reason) => (None, Some(reason.clone())),
+        _ => (None, None),
+    };
+    let nanos = |duration: Option<Duration>| duration.map(|duration| duration.as_nanos().to_string());
+    [
+        (Some(check.day.to_string()), true),
+        (Some(check.part.to_string()), true),
+        (row.profile.clone(), false),
+        (check.answer.as_ref().map(Answer::to_string), false),
+        (nanos(row.parse), true),
+        (nanos(row.solve), true),
+        (row.input_hash.clone(), false),
+        (Some(row.version.to_string()), false),
+        (Some(check.status.name().to_string()), false),
+        (expected, false),
+        (reason, false),
+    ]
+}
+
+pub(crate) fn to_json(rows: &[Row]) -> String {
+    let objects = rows.iter()
+        .map(|row| {
+            let fields = FIELDS.into_iter()
+                .zip(values(row))
+                .map(|(name, (value, numeric))| (name, match value {
+                    Some(value) if numeric => value,
+                    value => json::optional(value.as_deref()),
+                }))
+                .collect::<Vec<_>>();
+            format!("  {}", json::object(&fields))
+        })
+        .collect::<Vec<_>>();
+    format!("[\n{}\n]", objects.join(",\n"))
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub(crate) fn to_csv(rows: &[Row]) -> String {
+    let lines = rows.iter()
+        .map(|row| values(row)
+            .map(|(value, _)| value.as_deref().map_or_else(String::new, csv_field))
+            .join(","));
+    std::iter::once(FIELDS.join(",")).chain(lines).collect::<Vec<_>>().join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn row(day: u8, part: u8, answer: Option<u64>, status: Status, timed: bool) -> Row {
+        Row {
+            check: Check { day, part, answer: answer.map(Answer::Unsigned), status },
+            profile: None,
+            parse: timed.then(|| Duration::from_micros(15)),
+            solve: timed.then(|| Duration::from_micros(part as u64 + 2)),
+            input_hash: timed.then(|| "ab12".to_string()),
+            version: "0.1.0",
+            warnings: Vec::new(),
+        }
+    }
+
+    fn rows() -> Vec<Row> {
+        vec![
+            row(1, 1, Some(142), Status::Matching, true),
+            row(1, 2, Some(280), Status::Changed { expected: Answer::Unsigned(281) }, true),
+            row(5, 1, None, Status::Skipped("No input, \"here\"".to_string()), false),
+        ]
+    }
+
+    #[test]
+    fn test_run_all() {
+        let input_dir = std::env::temp_dir().join(format!("aoc-summary-run-all-{}", std::process::id()));
+        std::fs::create_dir_all(&input_dir).unwrap();
+        std::fs::write(input_dir.join("day01.txt"), "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n").unwrap();
+        std::fs::write(input_dir.join("day04.txt"), "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\n").unwrap();
+        let answers = Answers::parse("[day01]\npart1 = \"142\"\npart2 = \"142\"").unwrap();
+        let rows = run_all(&[&aoc_01::Day01, &aoc_02::Day02, &aoc_04::Day04], &answers, |_| Resolver {
+            input_dir: Some(input_dir.clone()),
+            ..Resolver::default()
+        });
+        std::fs::remove_dir_all(&input_dir).unwrap();
+        let statuses = rows.iter()
+            .map(|row| (row.check.day, row.check.part, row.check.status.name()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![(1, 1, "matching"), (1, 2, "matching"), (2, 1, "skipped"), (2, 2, "skipped"), (4, 1, "new"), (4, 2, "unsolved")],
+            statuses
+        );
+        assert_eq!(Some(Answer::Unsigned(8)), rows[4].check.answer);
+        assert!(rows.iter().all(|row| row.parse.is_some() == row.input_hash.is_some()));
+        assert!(rows[2].parse.is_none() && rows[5].solve.is_none());
+    }
+
+    #[test]
+    fn test_run_day() {
+        let input = Input::from("1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n");
+        let rows = run_day(&aoc_01::Day01, &input, &Options::default(), &[1, 2], Some(&Answers::default()));
+        assert_eq!(vec![Some(Answer::Unsigned(142)), Some(Answer::Unsigned(142))], rows.iter().map(|row| row.check.answer.clone()).collect::<Vec<_>>());
+        assert_eq!(Some(sha256_hex(input.as_bytes())), rows[0].input_hash);
+        assert_eq!("0.1.0", rows[0].version);
+
+        let rows = run_day(&aoc_01::Day01, &Input::from("eightwothree\n"), &Options::default(), &[1, 2], Some(&Answers::default()));
+        assert_eq!(vec!["failed", "new"], rows.iter().map(|row| row.check.status.name()).collect::<Vec<_>>());
+        let rows = run_day(&aoc_01::Day01, &Input::from("eightwothree\n"), &Options::default(), &[1, 2], None);
+        assert_eq!(vec!["failed", "unchecked"], rows.iter().map(|row| row.check.status.name()).collect::<Vec<_>>());
+
+        let streamed = stream_day(&aoc_01::Day01, &mut input.as_bytes(), &Options::default(), &[2], None).unwrap();
+        assert_eq!(Some(sha256_hex(input.as_bytes())), streamed[0].input_hash);
+        assert_eq!(None, streamed[0].solve);
+
+        let options = "vocabulary=digits+french".parse::<Options>().unwrap();
+        let rows = run_day(&aoc_01::Day01, &Input::from("un2trois\n"), &options, &[2], None);
+        assert_eq!(Some(Answer::Unsigned(13)), rows[0].check.answer);
+        assert!(warnings(&rows).is_empty());
+
+        let options = "missing=skip".parse::<Options>().unwrap();
+        let rows = run_day(&aoc_01::Day01, &Input::from("1abc2\nabc\n"), &options, &[1, 2], None);
+        assert_eq!(
+            vec![
+                "Day 1 part 1: 1 line(s) without at least one digit skipped: 2",
+                "Day 1 part 2: 1 line(s) without at least one digit skipped: 2",
+            ],
+            warnings(&rows)
+        );
+    }
+
+    #[test]
+    fn test_table() {
+        let table = Table(&rows()).to_string();
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!("Day  Part  Answer       Parse       Solve  Status", lines[0]);
+        assert_eq!("  1     1  142         15.0µs       3.0µs  ok", lines[1]);
+        assert!(lines[2].ends_with("CHANGED, expected 281"));
+        assert!(lines[3].starts_with("  5     1  -"));
+        assert_eq!("Day 5 part 1: No input, \"here\"", lines[5]);
+    }
+
+    #[test]
+    fn test_run_profiles() {
+        let dir = std::env::temp_dir().join(format!("aoc-summary-profiles-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("alice.txt"), "1abc2\npqr3stu8vwx\n").unwrap();
+        std::fs::write(dir.join("alice.expected"), "part1 = 50\npart2 = 50\n").unwrap();
+        std::fs::write(dir.join("bob.txt"), "two1nine\n").unwrap();
+        std::fs::write(dir.join("bob.expected"), "part2 = 30\n").unwrap();
+        std::fs::write(dir.join("carol.txt"), "eightwothree\n").unwrap();
+        let profiles = Profile::load_all(&dir, 1).unwrap();
+        let rows = run_profiles(&aoc_01::Day01, &profiles, &[1, 2]);
+        std::fs::remove_dir_all(&dir).unwrap();
+        let statuses = rows.iter()
+            .map(|row| (row.profile.as_deref().unwrap(), row.check.part, row.check.status.name()))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![("alice", 1, "matching"), ("alice", 2, "matching"), ("bob", 1, "new"), ("bob", 2, "changed"), ("carol", 1, "failed"), ("carol", 2, "new")], statuses);
+
+        let table = Table(&rows).to_string();
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!("Day  Part  Profile  Answer       Parse       Solve  Status", lines[0]);
+        assert!(lines[4].starts_with("  1     2  bob      29    "));
+        assert!(lines[4].ends_with("CHANGED, expected 30"));
+        assert!(lines[8].starts_with("Day 1 part 1 (carol): "));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let json = to_json(&rows());
+        let lines = json.lines().collect::<Vec<_>>();
+        assert_eq!(5, lines.len());
+        assert_eq!(
+            "  {\"day\": 1, \"part\": 2, \"profile\": null, \"answer\": \"280\", \"parse_ns\": 15000, \"solve_ns\": 4000, \"input_sha256\": \"ab12\", \"version\": \"0.1.0\", \"status\": \"changed\", \"expected\": \"281\", \"reason\": null},",
+            lines[2]
+        );
+        assert!(lines[3].ends_with("\"status\": \"skipped\", \"expected\": null, \"reason\": \"No input, \\\"here\\\"\"}"));
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let csv = to_csv(&rows());
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!("day,part,profile,answer,parse_ns,solve_ns,input_sha256,version,status,expected,reason", lines[0]);
+        assert_eq!("1,1,,142,15000,3000,ab12,0.1.0,matching,,", lines[1]);
+        assert_eq!("5,1,,,,,,0.1.0,skipped,,\"No input, \"\"here\"\"\"", lines[3]);
+    }
+}
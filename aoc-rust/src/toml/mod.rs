@@ -0,0 +1,184 @@
+use aoc_common::Error;
+
+// The small subset of TOML used by the runner's files: '[section]' headers followed
+// by 'key = value' entries, where values are integers or double-quoted strings.
+#[derive(Debug)]
+pub(crate) struct Section {
+    pub(crate) name: String,
+    pub(crate) entries: Vec<Entry>,
+    line: Line,
+}
+
+#[derive(Debug)]
+pub(crate) struct Entry {
+    pub(crate) key: String,
+    pub(crate) value: String,
+    line: Line,
+}
+
+#[derive(Debug)]
+struct Line {
+    number: usize,
+    text: String,
+}
+
+impl Line {
+    fn error(&self, expected: &str) -> Error {
+        Error::parse(&self.text, self.text.trim(), expected).at_line(self.number)
+    }
+}
+
+impl Section {
+    pub(crate) fn error(&self, expected: &str) -> Error {
+        self.line.error(expected)
+    }
+}
+
+impl Entry {
+    pub(crate) fn error(&self, expected: &str) -> Error {
+        self.line.error(expected)
+    }
+}
+
+pub(crate) fn parse(text: &str) -> aoc_common::Result<Vec<Section>> {
+    let mut sections = Vec::<Section>::new();
+    for (index, text) in text.lines().enumerate() {
+        let line = Line {
+            number: index + 1,
+            text: text.to_string(),
+        };
+        let trimmed = text.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if let Some(header) = trimmed.strip_prefix('[') {
+            let name = header.strip_suffix(']')
+                .filter(|name| !name.is_empty())
+                .ok_or_else(|| line.error("a '[section]' header"))?;
+            sections.push(Section {
+                name: name.to_string(),
+                entries: Vec::new(),
+                line,
+            });
+            continue;
+        }
+        let Some((key, value)) = trimmed.split_once('=') else {
+            return Err(line.error("'key = value'"));
+        };
+        let Some(section) = sections.last_mut() else {
+            return Err(line.error("a '[section]' header first"));
+        };
+        let value = value.trim();
+        let value = match value.strip_prefix('"') {
+            Some(quoted) => unquote(quoted).ok_or_else(|| line.error("a double-quoted string"))?,
+            None => value.to_string(),
+        };
+        section.entries.push(Entry {
+            key: key.trim().to_string(),
+            value,
+            line,
+        });
+    }
+    Ok(sections)
+}
+
+pub(crate) fn write_section(toml: &mut String, name: &str, entries: &[(&str, String)]) {
+    toml.push_str(&format!("\n[{}]\n", name));
+    for (key, value) in entries {
+        toml.push_str(&format!("{} = {}\n", key, value));
+    }
+}
+
+// Escapes what would end the string or the line, as TOML's basic strings do.
+pub(crate) fn quoted(value: impl std::fmt::Display) -> String {
+    let mut quoted = String::from('"');
+    for c in value.to_string().chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+// The string after an opening quote, up to the closing one that must end it.
+fn unquote(quoted: &str) -> Option<String> {
+    let mut value = String::new();
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return chars.as_str().is_empty().then_some(value),
+            '\\' => value.push(match chars.next()? {
+                '"' => '"',
+                '\\' => '\\',
+                'n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                _ => return None,
+            }),
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let sections = parse("# comment\n\n[day01]\npart1 = \"142\"\n\n[day01.parse]\nmedian_ns = 12\n").unwrap();
+        let flattened = sections.iter()
+            .flat_map(|section| section.entries.iter()
+                .map(|entry| (section.name.as_str(), entry.key.as_str(), entry.value.as_str())))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![("day01", "part1", "142"), ("day01.parse", "median_ns", "12")], flattened);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let line = |text: &str| match parse(text) {
+            Err(Error::Parse(error)) => error.line,
+            _ => panic!("Expected a parse error for '{}'", text),
+        };
+        assert_eq!(1, line("part1 = \"1\""));
+        assert_eq!(3, line("[day01]\n\n[]"));
+        assert_eq!(2, line("[day01]\npart1"));
+        assert_eq!(2, line("[day01]\npart1 = \"1"));
+        assert_eq!(2, line("[day01]\npart1 = \"1\" 2\""));
+        assert_eq!(2, line("[day01]\npart1 = \"\\x\""));
+    }
+
+    #[test]
+    fn test_quoted() {
+        for value in ["142", "a \"quoted\" answer", "C:\\path\\", "two\nlines\t"] {
+            let mut toml = String::new();
+            write_section(&mut toml, "day01", &[("part1", quoted(value))]);
+            assert_eq!(value, parse(&toml).unwrap()[0].entries[0].value);
+        }
+        assert_eq!("\"a \\\"b\\\" \\\\ c\"", quoted("a \"b\" \\ c"));
+    }
+
+    #[test]
+    fn test_entry_error() {
+        let sections = parse("[day01]\n\npart3 = \"1\"").unwrap();
+        match sections[0].entries[0].error("'part1' or 'part2'") {
+            Error::Parse(error) => assert_eq!((3, 1, "part3 = \"1\""), (error.line, error.column, error.text.as_str())),
+            error => panic!("Unexpected error {}", error),
+        }
+    }
+
+    #[test]
+    fn test_write_section() {
+        let mut toml = String::new();
+        write_section(&mut toml, "day01", &[("part1", quoted(142)), ("median_ns", 12.to_string())]);
+        assert_eq!("\n[day01]\npart1 = \"142\"\nmedian_ns = 12\n", toml);
+        assert_eq!(1, parse(&toml).unwrap().len());
+    }
+}
@@ -0,0 +1,168 @@
+use std::fmt;
+use aoc_common::{Answer, Runner};
+use aoc_common::input::resolve::Resolver;
+use crate::answers::Answers;
+
+#[derive(Debug)]
+pub(crate) enum Status {
+    Matching,
+    Changed {
+        expected: Answer,
+    },
+    New,
+    Unchecked,
+    Unsolved,
+    Skipped(String),
+    Failed(String),
+}
+
+#[derive(Debug)]
+pub(crate) struct Check {
+    pub(crate) day: u8,
+    pub(crate) part: u8,
+    pub(crate) answer: Option<Answer>,
+    pub(crate) status: Status,
+}
+
+impl Status {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Status::Matching => "matching",
+            Status::Changed { .. } => "changed",
+            Status::New => "new",
+            Status::Unchecked => "unchecked",
+            Status::Unsolved => "unsolved",
+            Status::Skipped(_) => "skipped",
+            Status::Failed(_) => "failed",
+        }
+    }
+
+    pub(crate) fn label(&self) -> String {
+        match self {
+            Status::Matching => "ok".to_string(),
+            Status::Changed { expected } => format!("CHANGED, expected {}", expected),
+            Status::New => "not accepted yet".to_string(),
+            Status::Unchecked => "-".to_string(),
+            Status::Unsolved => "unsolved".to_string(),
+            Status::Skipped(_) => "skipped".to_string(),
+            Status::Failed(_) => "FAILED".to_string(),
+        }
+    }
+}
+
+impl Check {
+    pub(crate) fn is_regression(&self) -> bool {
+        matches!(self.status, Status::Changed { .. } | Status::Failed(_))
+    }
+}
+
+// Without accepted answers to compare with, as for an input other than the puzzle's own,
+// an answer is reported unchecked.
+pub(crate) fn check(day: u8, part: u8, result: aoc_common::Result<Answer>, answers: Option<&Answers>) -> Check {
+    let expected = answers.and_then(|answers| answers.get(day, part));
+    let (answer, status) = match result {
+        Ok(answer) => {
+            let status = match expected {
+                None if answers.is_none() => Status::Unchecked,
+                None => Status::New,
+                Some(expected) if *expected == answer => Status::Matching,
+                Some(expected) => Status::Changed { expected: expected.clone() },
+            };
+            (Some(answer), status)
+        }
+        Err(aoc_common::Error::Unsolved { .. }) if expected.is_none() => (None, Status::Unsolved),
+        Err(error) => (None, Status::Failed(error.to_string())),
+    };
+    Check { day, part, answer, status }
+}
+
+// Without an input, only the parts that have an accepted answer are regressions.
+pub(crate) fn without_input(day: u8, part: u8, error: &aoc_common::Error, answers: Option<&Answers>) -> Check {
+    Check {
+        day,
+        part,
+        answer: None,
+        status: match answers.and_then(|answers| answers.get(day, part)) {
+            Some(_) => Status::Failed(error.to_string()),
+            None => Status::Skipped(error.to_string()),
+        },
+    }
+}
+
+pub(crate) fn verify(days: &[&dyn Runner], answers: &Answers) -> Vec<Check> {
+    let mut checks = Vec::new();
+    for day in days {
+        let input = Resolver::from_env(None, day.manifest_dir())
+            .resolve(day.day())
+            .and_then(aoc_common::Input::read);
+        for part in 1..=2 {
+            checks.push(match &input {
+                Ok(input) => check(day.day(), part, day.run(input, &[part]).map(|mut answers| answers.remove(0)), Some(answers)),
+                Err(error) => without_input(day.day(), part, error, Some(answers)),
+            });
+        }
+    }
+    checks
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Day {} part {}: ", self.day, self.part)?;
+        match (&self.status, &self.answer) {
+            (Status::Matching, Some(answer)) => write!(f, "{} ok", answer),
+            (Status::Changed { expected }, Some(answer)) => write!(f, "{} CHANGED, expected {}", answer, expected),
+            (Status::New, Some(answer)) => write!(f, "{} not accepted yet", answer),
+            (Status::Unchecked, Some(answer)) => write!(f, "{}", answer),
+            (Status::Skipped(reason), _) => write!(f, "skipped, {}", reason),
+            (Status::Failed(error), _) => write!(f, "FAILED, {}", error),
+            _ => write!(f, "unsolved"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn answers() -> Answers {
+        Answers::parse("[day01]\npart1 = \"142\"\npart2 = \"281\"").unwrap()
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = answers();
+
+        let matching = check(1, 1, Ok(Answer::Unsigned(142)), Some(&answers));
+        assert!(matches!(matching.status, Status::Matching));
+        assert!(!matching.is_regression());
+
+        let changed = check(1, 2, Ok(Answer::Unsigned(280)), Some(&answers));
+        assert!(matches!(&changed.status, Status::Changed { expected } if *expected == Answer::Unsigned(281)));
+        assert!(changed.is_regression());
+        assert_eq!("Day 1 part 2: 280 CHANGED, expected 281", changed.to_string());
+
+        let new = check(2, 1, Ok(Answer::Unsigned(8)), Some(&answers));
+        assert!(matches!(new.status, Status::New));
+        assert!(!new.is_regression());
+
+        let unchecked = check(1, 2, Ok(Answer::Unsigned(280)), None);
+        assert!(matches!(unchecked.status, Status::Unchecked));
+        assert!(!unchecked.is_regression());
+        assert_eq!("Day 1 part 2: 280", unchecked.to_string());
+    }
+
+    #[test]
+    fn test_check_errors() {
+        let answers = answers();
+
+        let unsolved = check(4, 2, Err(aoc_common::Error::Unsolved { day: 4, part: 2 }), Some(&answers));
+        assert!(matches!(unsolved.status, Status::Unsolved));
+        assert!(!unsolved.is_regression());
+
+        let no_longer_solved = check(1, 2, Err(aoc_common::Error::Unsolved { day: 1, part: 2 }), Some(&answers));
+        assert!(no_longer_solved.is_regression());
+
+        let failed = check(2, 1, Err(aoc_common::Error::parse("x", "x", "a game")), Some(&answers));
+        assert!(failed.is_regression());
+    }
+}
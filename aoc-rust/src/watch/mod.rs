@@ -0,0 +1,392 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+use std::time::{Duration, SystemTime};
+use aoc_common::{Answer, Runner};
+use aoc_common::input::resolve::Resolver;
+use crate::bench;
+use crate::summary;
+use crate::verify::Status;
+
+const POLL: Duration = Duration::from_millis(500);
+// Editors save in several writes, so a change is only acted on once the files stop moving.
+const SETTLE: Duration = Duration::from_millis(200);
+const CLEAR: &str = "\x1b[H\x1b[2J";
+const ERROR_LINES: usize = 30;
+
+// The watched files' modification times and sizes.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Snapshot(BTreeMap<PathBuf, (Option<SystemTime>, u64)>);
+
+impl Snapshot {
+    pub(crate) fn take(paths: &[PathBuf]) -> Self {
+        let mut snapshot = Snapshot::default();
+        for path in paths {
+            snapshot.add(path);
+        }
+        snapshot
+    }
+
+    fn add(&mut self, path: &Path) {
+        let Ok(metadata) = std::fs::metadata(path) else {
+            return;
+        };
+        if metadata.is_dir() {
+            for entry in std::fs::read_dir(path).into_iter().flatten().flatten() {
+                self.add(&entry.path());
+            }
+        } else {
+            self.0.insert(path.to_path_buf(), (metadata.modified().ok(), metadata.len()));
+        }
+    }
+}
+
+// The day crate's sources, manifest and build script, and its input wherever it was found.
+fn watched(runner: &dyn Runner) -> Vec<PathBuf> {
+    let manifest_dir = Path::new(runner.manifest_dir());
+    let mut paths = vec![manifest_dir.join("src"), manifest_dir.join("Cargo.toml"), manifest_dir.join("build.rs")];
+    if let Ok(input) = Resolver::from_env(None, runner.manifest_dir()).resolve(runner.day()) {
+        paths.push(input);
+    }
+    paths
+}
+
+fn wait_for_change(runner: &dyn Runner, previous: &Snapshot) -> Snapshot {
+    let mut snapshot = Snapshot::take(&watched(runner));
+    while snapshot == *previous {
+        std::thread::sleep(POLL);
+        snapshot = Snapshot::take(&watched(runner));
+    }
+    loop {
+        std::thread::sleep(SETTLE);
+        let settled = Snapshot::take(&watched(runner));
+        if settled == snapshot {
+            return settled;
+        }
+        snapshot = settled;
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Record {
+    pub(crate) part: u8,
+    pub(crate) answer: Option<String>,
+    pub(crate) parse: Option<Duration>,
+    pub(crate) solve: Option<Duration>,
+    pub(crate) status: Status,
+}
+
+// Splits the CSV written by 'summary::to_csv' back into records of fields.
+fn split_csv(text: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(char) = chars.next() {
+        match char {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => record.push(std::mem::take(&mut field)),
+            '\n' if !quoted => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            char => field.push(char),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+fn status(name: &str, expected: Option<String>, reason: Option<String>) -> Option<Status> {
+    Some(match name {
+        "matching" => Status::Matching,
+        "changed" => Status::Changed { expected: expected?.parse::<Answer>().ok()? },
+        "new" => Status::New,
+        "unchecked" => Status::Unchecked,
+        "unsolved" => Status::Unsolved,
+        "skipped" => Status::Skipped(reason?),
+        "failed" => Status::Failed(reason?),
+        _ => return None,
+    })
+}
+
+pub(crate) fn parse_records(csv: &str) -> Option<Vec<Record>> {
+    let mut records = split_csv(csv).into_iter();
+    if records.next()? != summary::FIELDS {
+        return None;
+    }
+    records
+        .map(|values| {
+            let field = |name: &str| summary::FIELDS.iter()
+                .position(|field| *field == name)
+                .and_then(|index| values.get(index))
+                .filter(|value| !value.is_empty())
+                .cloned();
+            let nanos = |name: &str| field(name)
+                .and_then(|value| value.parse::<u64>().ok())
+                .map(Duration::from_nanos);
+            Some(Record {
+                part: field("part")?.parse().ok()?,
+                answer: field("answer"),
+                parse: nanos("parse_ns"),
+                solve: nanos("solve_ns"),
+                status: status(&field("status")?, field("expected"), field("reason"))?,
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Tests {
+    pub(crate) passed: usize,
+    pub(crate) failed: usize,
+    pub(crate) failures: Vec<String>,
+}
+
+// Adds up the 'test result:' lines of every test binary; there are none when the build failed.
+pub(crate) fn parse_tests(output: &str) -> Option<Tests> {
+    let mut tests = Tests::default();
+    let mut finished = false;
+    for line in output.lines() {
+        if let Some(name) = line.strip_prefix("---- ").and_then(|line| line.strip_suffix(" stdout ----")) {
+            tests.failures.push(name.to_string());
+        }
+        let Some((_, counts)) = line.split_once("test result: ") else {
+            continue;
+        };
+        finished = true;
+        for count in counts.split([';', '.']) {
+            match count.trim().split_once(' ') {
+                Some((number, "passed")) => tests.passed += number.parse::<usize>().unwrap_or(0),
+                Some((number, "failed")) => tests.failed += number.parse::<usize>().unwrap_or(0),
+                _ => {}
+            }
+        }
+    }
+    finished.then_some(tests)
+}
+
+pub(crate) struct Cycle {
+    pub(crate) records: Result<Vec<Record>, String>,
+    pub(crate) tests: Result<Tests, String>,
+}
+
+fn last_lines(text: &str) -> String {
+    let lines = text.trim_end().lines().collect::<Vec<_>>();
+    lines[lines.len().saturating_sub(ERROR_LINES)..].join("\n")
+}
+
+// Builds go to their own target directory, so that the running binary is never replaced under us.
+fn cargo(workspace: &Path, args: &[&str]) -> aoc_common::Result<Output> {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let mut command = std::process::Command::new(&cargo);
+    command.arg(args[0]).arg("-q");
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    command.args(&args[1..])
+        .current_dir(workspace)
+        .env("CARGO_TARGET_DIR", workspace.join("target").join("watch"))
+        .output()
+        .map_err(|source| aoc_common::Error::Io { path: PathBuf::from(cargo), source })
+}
+
+fn cycle(runner: &dyn Runner, workspace: &Path) -> aoc_common::Result<Cycle> {
+    let day = runner.day().to_string();
+    let run = cargo(workspace, &["run", "-p", "aoc", "--", "run", "--day", &day, "--format", "csv"])?;
+    let records = parse_records(&String::from_utf8_lossy(&run.stdout))
+        .ok_or_else(|| last_lines(&String::from_utf8_lossy(&run.stderr)));
+
+    let manifest = Path::new(runner.manifest_dir()).join("Cargo.toml");
+    let test = cargo(workspace, &["test", "--manifest-path", &manifest.to_string_lossy()])?;
+    let tests = parse_tests(&String::from_utf8_lossy(&test.stdout))
+        .ok_or_else(|| last_lines(&String::from_utf8_lossy(&test.stderr)));
+    Ok(Cycle { records, tests })
+}
+
+fn duration(duration: Option<Duration>) -> String {
+    duration.map_or_else(|| "-".to_string(), |duration| format!("{:.1?}", duration))
+}
+
+fn change(before: Option<Duration>, after: Option<Duration>) -> String {
+    match (before, after) {
+        (Some(before), Some(after)) => bench::change(before, after),
+        _ => String::new(),
+    }
+}
+
+pub(crate) struct View<'a> {
+    pub(crate) day: u8,
+    pub(crate) run: usize,
+    pub(crate) cycle: &'a Cycle,
+    pub(crate) previous: Option<&'a [Record]>,
+}
+
+impl fmt::Display for View<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Day {}, run {}, watching for changes (Ctrl-C to stop)\n\n", self.day, self.run)?;
+        match &self.cycle.records {
+            Ok(records) => {
+                let width = records.iter()
+                    .filter_map(|record| record.answer.as_ref())
+                    .map(String::len)
+                    .fold("Answer".len(), usize::max);
+                write!(f, "{:>4}  {:<width$}  {:>10}  {:>8}  {:>10}  {:>8}  Status", "Part", "Answer", "Parse", "Change", "Solve", "Change")?;
+                for record in records {
+                    let before = self.previous
+                        .and_then(|previous| previous.iter().find(|before| before.part == record.part));
+                    write!(
+                        f,
+                        "\n{:>4}  {:<width$}  {:>10}  {:>8}  {:>10}  {:>8}  {}",
+                        record.part,
+                        record.answer.as_deref().unwrap_or("-"),
+                        duration(record.parse),
+                        change(before.and_then(|before| before.parse), record.parse),
+                        duration(record.solve),
+                        change(before.and_then(|before| before.solve), record.solve),
+                        record.status.label(),
+                    )?;
+                }
+                for record in records {
+                    if let Status::Skipped(reason) | Status::Failed(reason) = &record.status {
+                        write!(f, "\n\nPart {}: {}", record.part, reason)?;
+                    }
+                }
+            }
+            Err(error) => write!(f, "Run failed:\n{}", error)?,
+        }
+        match &self.cycle.tests {
+            Ok(tests) => {
+                write!(f, "\n\nTests: {} passed, {} failed", tests.passed, tests.failed)?;
+                for failure in &tests.failures {
+                    write!(f, "\n    FAILED {}", failure)?;
+                }
+                Ok(())
+            }
+            Err(error) => write!(f, "\n\nTests failed to build:\n{}", error),
+        }
+    }
+}
+
+// Runs the day and its tests, then again every time one of the watched files changes.
+pub(crate) fn watch(runner: &dyn Runner, workspace: &Path) -> aoc_common::Result<()> {
+    let mut snapshot = Snapshot::default();
+    let mut previous = None::<Vec<Record>>;
+    for run in 1.. {
+        snapshot = wait_for_change(runner, &snapshot);
+        println!("\nBuilding...");
+        let cycle = cycle(runner, workspace)?;
+        println!("{}{}", CLEAR, View { day: runner.day(), run, cycle: &cycle, previous: previous.as_deref() });
+        // A failed build keeps the last good timings to compare against.
+        if let Ok(records) = cycle.records {
+            previous = Some(records);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CSV: &str = "\
+day,part,profile,answer,parse_ns,solve_ns,input_sha256,version,status,expected,reason
+1,1,,142,15000,3000,ab12,0.1.0,changed,143,
+1,2,,,,,,0.1.0,failed,,\"Bad line, \"\"x\"\"\"";
+
+    fn record(part: u8, answer: &str, solve: u64) -> Record {
+        Record {
+            part,
+            answer: Some(answer.to_string()),
+            parse: Some(Duration::from_micros(10)),
+            solve: Some(Duration::from_micros(solve)),
+            status: Status::Matching,
+        }
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-test-snapshot-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("src/input")).unwrap();
+        std::fs::write(dir.join("src/lib.rs"), "a").unwrap();
+        let paths = [dir.join("src"), dir.join("missing.txt")];
+
+        let before = Snapshot::take(&paths);
+        assert_eq!(1, before.0.len());
+        assert_eq!(before, Snapshot::take(&paths));
+        std::fs::write(dir.join("src/input/input.txt"), "1").unwrap();
+        assert_ne!(before, Snapshot::take(&paths));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_records() {
+        let records = parse_records(CSV).unwrap();
+        assert_eq!(2, records.len());
+        assert_eq!(Some("142".to_string()), records[0].answer);
+        assert_eq!(Some(Duration::from_micros(3)), records[0].solve);
+        assert!(matches!(&records[0].status, Status::Changed { expected } if *expected == Answer::Unsigned(143)));
+        assert_eq!(None, records[1].parse);
+        assert!(matches!(&records[1].status, Status::Failed(reason) if reason == "Bad line, \"x\""));
+
+        assert!(parse_records("").is_none());
+        assert!(parse_records("error: could not compile").is_none());
+        assert!(parse_records(&CSV.replace("changed", "odd")).is_none());
+    }
+
+    #[test]
+    fn test_parse_tests() {
+        let output = "\
+running 3 tests
+..F
+failures:
+
+---- examples::example_1_part2 stdout ----
+assertion failed
+
+failures:
+    examples::example_1_part2
+
+test result: FAILED. 2 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+running 0 tests
+
+test result: ok. 0 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+";
+        let tests = parse_tests(output).unwrap();
+        assert_eq!(Tests { passed: 2, failed: 1, failures: vec!["examples::example_1_part2".to_string()] }, tests);
+        assert_eq!(None, parse_tests(""));
+    }
+
+    #[test]
+    fn test_view() {
+        let cycle = Cycle {
+            records: Ok(vec![record(1, "142", 3), record(2, "281", 8)]),
+            tests: Ok(Tests { passed: 4, failed: 1, failures: vec!["part1::test".to_string()] }),
+        };
+        let previous = [record(1, "142", 2)];
+        let view = View { day: 1, run: 2, cycle: &cycle, previous: Some(&previous) }.to_string();
+        let lines = view.lines().collect::<Vec<_>>();
+        assert_eq!("Day 1, run 2, watching for changes (Ctrl-C to stop)", lines[0]);
+        assert_eq!("Part  Answer       Parse    Change       Solve    Change  Status", lines[2]);
+        assert_eq!("   1  142         10.0µs     +0.0%       3.0µs    +50.0%  ok", lines[3]);
+        assert_eq!("   2  281         10.0µs                 8.0µs            ok", lines[4]);
+        assert_eq!("Tests: 4 passed, 1 failed", lines[6]);
+        assert_eq!("    FAILED part1::test", lines[7]);
+
+        let broken = Cycle { records: Err("error[E0308]: mismatched types".to_string()), tests: Err("error".to_string()) };
+        let view = View { day: 1, run: 3, cycle: &broken, previous: None }.to_string();
+        assert!(view.contains("Run failed:\nerror[E0308]: mismatched types"));
+        assert!(view.ends_with("Tests failed to build:\nerror"));
+    }
+}